@@ -0,0 +1,90 @@
+//! Benchmarks for `correct_command` with session metadata sizes we see in practice:
+//! thousands of executables on the PATH and a long shell history.
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+
+use command_corrections::{correct_command, Command, ExitCode, SessionMetadata};
+
+const HISTORY_SIZE: usize = 100_000;
+const EXECUTABLE_COUNTS: &[usize] = &[5_000, 20_000, 50_000];
+
+/// Deterministic, executable-looking names (e.g. "kubectl-bexa3", "git-zuro12").
+fn generate_names(count: usize, seed: u64) -> Vec<String> {
+    const PREFIXES: &[&str] = &[
+        "git", "docker", "kubectl", "python3", "npm", "cargo", "brew", "lib", "x86_64", "gcc",
+        "perl", "ruby", "java", "node", "aws", "gcloud", "pip", "yarn", "clang", "llvm",
+    ];
+    const SYLLABLES: &[&str] = &["ba", "ke", "zo", "ru", "mi", "ta", "xe", "lo", "pu", "di"];
+
+    let mut state = seed;
+    let mut next = move || {
+        // xorshift64
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state as usize
+    };
+
+    (0..count)
+        .map(|i| {
+            let prefix = PREFIXES[next() % PREFIXES.len()];
+            let suffix = (0..1 + next() % 3)
+                .map(|_| SYLLABLES[next() % SYLLABLES.len()])
+                .collect::<String>();
+            format!("{prefix}-{suffix}{i}")
+        })
+        .collect()
+}
+
+fn bench_no_command(c: &mut Criterion) {
+    let history = generate_names(HISTORY_SIZE, 7)
+        .into_iter()
+        .map(|name| format!("{name} --flag value"))
+        .collect::<Vec<_>>();
+
+    let mut group = c.benchmark_group("no_command");
+    for &count in EXECUTABLE_COUNTS {
+        let mut executables = generate_names(count, 42);
+        executables.extend(["git", "cargo", "docker"].map(str::to_owned));
+
+        let mut metadata = SessionMetadata::new();
+        metadata.set_executables(executables.iter().map(String::as_str));
+        metadata.set_history(history.iter().map(String::as_str));
+
+        group.bench_with_input(
+            BenchmarkId::from_parameter(count),
+            &metadata,
+            |b, metadata| {
+                b.iter(|| {
+                    let command = Command::new(
+                        "gti status",
+                        "zsh: command not found: gti",
+                        ExitCode::from(127),
+                    );
+                    black_box(correct_command(command, metadata))
+                })
+            },
+        );
+    }
+    group.finish();
+}
+
+fn bench_git_checkout(c: &mut Criterion) {
+    let branches = generate_names(5_000, 3);
+    let mut metadata = SessionMetadata::new();
+    metadata.set_git_branches(branches.iter().map(String::as_str));
+
+    c.bench_function("git_checkout_5000_branches", |b| {
+        b.iter(|| {
+            let command = Command::new(
+                "git checkout mian",
+                "error: pathspec 'mian' did not match any file(s) known to git",
+                ExitCode::from(1),
+            );
+            black_box(correct_command(command, &metadata))
+        })
+    });
+}
+
+criterion_group!(benches, bench_no_command, bench_git_checkout);
+criterion_main!(benches);
@@ -0,0 +1,31 @@
+//! Prints how every rule was evaluated for a command. Useful when a correction
+//! you expected doesn't show up.
+//!
+//! Usage: cargo run --example trace -- <input> <output> [exit code]
+//!
+//! Example: cargo run --example trace -- "git chekout main" "git: 'chekout' is not a git command" 1
+
+use std::{env, process};
+
+use command_corrections::{correct_command_traced, Command, ExitCode, SessionMetadata};
+
+fn main() {
+    let args = env::args().skip(1).collect::<Vec<_>>();
+    let (input, output) = match (args.first(), args.get(1)) {
+        (Some(input), Some(output)) => (input, output),
+        _ => {
+            eprintln!("usage: trace <input> <output> [exit code]");
+            process::exit(2);
+        }
+    };
+    let exit_code = args.get(2).and_then(|code| code.parse().ok()).unwrap_or(1);
+
+    let command = Command::new(input, output, ExitCode::from(exit_code));
+    let (corrections, trace) = correct_command_traced(command, &SessionMetadata::new());
+
+    print!("{trace}");
+    println!("Corrections:");
+    for correction in corrections {
+        println!("  {} ({})", correction.command, correction.rule_applied);
+    }
+}
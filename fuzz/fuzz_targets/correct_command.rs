@@ -0,0 +1,44 @@
+#![no_main]
+
+use arbitrary::Arbitrary;
+use command_corrections::{correct_command, Command, SessionMetadata, SessionType, Shell};
+use libfuzzer_sys::fuzz_target;
+
+#[derive(Arbitrary, Debug)]
+struct Input<'a> {
+    input: &'a str,
+    output: &'a str,
+    exit_code: usize,
+    in_working_dir: bool,
+    fish: bool,
+    remote: bool,
+    executables: Vec<&'a str>,
+    aliases: Vec<&'a str>,
+    history: Vec<&'a str>,
+    git_branches: Vec<&'a str>,
+}
+
+fuzz_target!(|data: Input| {
+    let mut metadata = SessionMetadata::new();
+    metadata.set_executables(data.executables);
+    metadata.set_aliases(data.aliases);
+    metadata.set_history(data.history);
+    metadata.set_git_branches(data.git_branches);
+    if data.fish {
+        metadata.set_shell(Shell::Fish);
+    }
+    if data.remote {
+        metadata.set_session_type(SessionType::Remote);
+    }
+
+    let mut command = Command::new(data.input, data.output, data.exit_code.into());
+    // A fixed directory that doesn't exist, so rules that look at the working directory run
+    // without the fuzzer reading (or depending on) the real filesystem.
+    if data.in_working_dir {
+        command = command.set_working_dir("/nonexistent/command-corrections-fuzz");
+    }
+
+    for correction in correct_command(command, &metadata) {
+        assert_ne!(correction.command, data.input.trim());
+    }
+});
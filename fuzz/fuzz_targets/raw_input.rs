@@ -0,0 +1,18 @@
+#![no_main]
+
+use command_corrections::{correct_command, Command, SessionMetadata};
+use libfuzzer_sys::fuzz_target;
+
+// Feeds raw (possibly malformed) shell input through the default pipeline. The output
+// is the input itself, which makes rules that extract words from the output see
+// the same odd quoting.
+fuzz_target!(|data: &[u8]| {
+    if let Ok(input) = std::str::from_utf8(data) {
+        for exit_code in [1, 127] {
+            let command = Command::new(input, input, exit_code.into());
+            for correction in correct_command(command, &SessionMetadata::new()) {
+                assert_ne!(correction.command, input.trim());
+            }
+        }
+    }
+});
@@ -0,0 +1,118 @@
+//! What rules know about the git repository a command ran in (see `SessionMetadata::set_git_state`).
+//! For local sessions, it's read from the repository's `.git` directory unless it's been set.
+
+use std::collections::BTreeMap;
+
+/// The refs and configuration of a repository that rules use for their corrections,
+/// e.g. to correct a misspelled remote or to check out a branch that only exists on a remote.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct GitState {
+    /// `None` if `HEAD` is detached (or unknown).
+    pub current_branch: Option<String>,
+    pub local_branches: Vec<String>,
+    /// e.g. `origin`.
+    pub remotes: Vec<String>,
+    /// The URL of each remote that has one, e.g. `origin` => `git@github.com:org/repo.git`.
+    pub remote_urls: BTreeMap<String, String>,
+    /// The remote-tracking branches of each remote, without the remote's name,
+    /// e.g. `origin` => [`main`, `feature`] for `origin/main` and `origin/feature`.
+    pub remote_branches: BTreeMap<String, Vec<String>>,
+    pub tags: Vec<String>,
+    /// The upstream of each local branch that has one, e.g. `main` => `origin/main`.
+    pub upstreams: BTreeMap<String, Upstream>,
+    /// The operation that stopped halfway (e.g. on a conflict), if any.
+    pub operation: Option<GitOperation>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Upstream {
+    pub remote: String,
+    pub branch: String,
+}
+
+/// An operation that's in progress in the repository and waits for the user to
+/// `--continue` or `--abort` it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GitOperation {
+    Merge,
+    Rebase,
+    CherryPick,
+    Revert,
+    /// `git am`
+    Am,
+    Bisect,
+}
+
+impl GitState {
+    pub fn has_local_branch(&self, branch: &str) -> bool {
+        self.local_branches.iter().any(|local| local == branch)
+    }
+
+    /// The remotes that have a branch with this name, e.g. `feature` => [`origin`].
+    pub fn remotes_with_branch<'s>(&'s self, branch: &'s str) -> impl Iterator<Item = &'s str> {
+        self.remote_branches
+            .iter()
+            .filter(move |(_, branches)| branches.iter().any(|b| b == branch))
+            .map(|(remote, _)| remote.as_str())
+    }
+
+    /// Remote-tracking branches as they're written in commands, e.g. `origin/main`.
+    pub fn qualified_remote_branches(&self) -> impl Iterator<Item = String> + '_ {
+        self.remote_branches.iter().flat_map(|(remote, branches)| {
+            branches
+                .iter()
+                .map(move |branch| format!("{remote}/{branch}"))
+        })
+    }
+
+    /// Remotes we know about, either configured or with remote-tracking branches.
+    pub fn all_remotes(&self) -> impl Iterator<Item = &str> {
+        let mut remotes = self
+            .remotes
+            .iter()
+            .chain(self.remote_branches.keys())
+            .map(String::as_str)
+            .collect::<Vec<_>>();
+        remotes.sort_unstable();
+        remotes.dedup();
+        remotes.into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::GitState;
+
+    #[test]
+    fn test_remote_branches() {
+        let state = GitState {
+            remotes: vec!["origin".to_owned()],
+            remote_branches: [
+                ("origin", vec!["main", "feature"]),
+                ("upstream", vec!["main"]),
+            ]
+            .into_iter()
+            .map(|(remote, branches)| {
+                (
+                    remote.to_owned(),
+                    branches.into_iter().map(str::to_owned).collect(),
+                )
+            })
+            .collect(),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            state.remotes_with_branch("main").collect::<Vec<_>>(),
+            vec!["origin", "upstream"]
+        );
+        assert_eq!(
+            state.qualified_remote_branches().collect::<Vec<_>>(),
+            vec!["origin/main", "origin/feature", "upstream/main"]
+        );
+        assert_eq!(
+            state.all_remotes().collect::<Vec<_>>(),
+            vec!["origin", "upstream"]
+        );
+    }
+}
@@ -0,0 +1,193 @@
+/// The (Latin) keyboard layouts we know about. Only the unshifted characters of the four
+/// main rows are modeled, which is enough to tell which keys are next to each other.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Layout {
+    #[default]
+    Qwerty,
+    Azerty,
+    Qwertz,
+    Dvorak,
+    Colemak,
+}
+
+/// Non-Latin layouts. When one of these is active by mistake, typing `git status`
+/// produces e.g. `пше ыефегы`; see `ForeignLayout::transliterate`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ForeignLayout {
+    /// Russian (ЙЦУКЕН).
+    Cyrillic,
+    Greek,
+    /// Hebrew (SI-1452).
+    Hebrew,
+}
+
+// Each layout is described by the characters on its four main rows, where the
+// same (row, column) is the same physical key on every layout.
+const QWERTY: [&str; 4] = ["1234567890-=", "qwertyuiop[]", "asdfghjkl;'", "zxcvbnm,./"];
+const AZERTY: [&str; 4] = ["&é\"'(-è_çà)=", "azertyuiop^$", "qsdfghjklmù", "wxcvbn,;:!"];
+const QWERTZ: [&str; 4] = ["1234567890ß´", "qwertzuiopü+", "asdfghjklöä", "yxcvbnm,.-"];
+const DVORAK: [&str; 4] = ["1234567890[]", "',.pyfgcrl/=", "aoeuidhtns-", ";qjkxbmwvz"];
+const COLEMAK: [&str; 4] = ["1234567890-=", "qwfpgjluy;[]", "arstdhneio'", "zxcvbkm,./"];
+
+const CYRILLIC: [&str; 4] = ["1234567890-=", "йцукенгшщзхъ", "фывапролджэ", "ячсмитьбю."];
+const GREEK: [&str; 4] = ["1234567890-=", ";ςερτυθιοπ[]", "ασδφγηξκλ΄'", "ζχψωβνμ,./"];
+const HEBREW: [&str; 4] = ["1234567890-=", "/'קראטוןםפ][", "שדגכעיחלךף,", "זסבהנמצתץ."];
+
+type KeyPosition = (usize, usize);
+
+fn position_in(rows: &[&str; 4], c: char) -> Option<KeyPosition> {
+    rows.iter()
+        .enumerate()
+        .find_map(|(row, keys)| keys.chars().position(|key| key == c).map(|col| (row, col)))
+}
+
+fn key_at(rows: &[&str; 4], (row, col): KeyPosition) -> Option<char> {
+    rows.get(row)?.chars().nth(col)
+}
+
+impl Layout {
+    fn rows(&self) -> &'static [&'static str; 4] {
+        use Layout::*;
+        match self {
+            Qwerty => &QWERTY,
+            Azerty => &AZERTY,
+            Qwertz => &QWERTZ,
+            Dvorak => &DVORAK,
+            Colemak => &COLEMAK,
+        }
+    }
+
+    /// Whether two characters are on keys next to each other (ignoring case).
+    pub fn are_adjacent(&self, a: char, b: char) -> bool {
+        let position = |c: char| position_in(self.rows(), c.to_lowercase().next()?);
+        let (Some((row_a, col_a)), Some((row_b, col_b))) = (position(a), position(b)) else {
+            return false;
+        };
+
+        // Rows are staggered, so the key "below-left" of a key is still adjacent
+        // (e.g. on QWERTY, "s" is adjacent to "w" and "e", and to "z" and "x").
+        if row_a == row_b {
+            col_a.abs_diff(col_b) == 1
+        } else if row_b == row_a + 1 {
+            col_b == col_a || col_b + 1 == col_a
+        } else if row_a == row_b + 1 {
+            col_a == col_b || col_a + 1 == col_b
+        } else {
+            false
+        }
+    }
+}
+
+impl ForeignLayout {
+    fn rows(&self) -> &'static [&'static str; 4] {
+        use ForeignLayout::*;
+        match self {
+            Cyrillic => &CYRILLIC,
+            Greek => &GREEK,
+            Hebrew => &HEBREW,
+        }
+    }
+
+    /// The foreign layout whose script the character belongs to (if any).
+    pub fn of(c: char) -> Option<Self> {
+        match c {
+            '\u{0400}'..='\u{04FF}' => Some(ForeignLayout::Cyrillic),
+            '\u{0370}'..='\u{03FF}' => Some(ForeignLayout::Greek),
+            '\u{0590}'..='\u{05FF}' => Some(ForeignLayout::Hebrew),
+            _ => None,
+        }
+    }
+
+    /// The foreign layout that was most likely used to type the input, if any.
+    pub fn detect(input: &str) -> Option<Self> {
+        input.chars().find_map(ForeignLayout::of)
+    }
+
+    /// Whether all the letters of the word are in this layout's script (and there's at least one),
+    /// e.g. `ыефегы` or `--ыефе` for Cyrillic.
+    pub fn is_script_of(&self, word: &str) -> bool {
+        let mut letters = word.chars().filter(|c| c.is_alphabetic()).peekable();
+        letters.peek().is_some() && letters.all(|c| ForeignLayout::of(c) == Some(*self))
+    }
+
+    /// Converts input that was typed with this layout active into what would've been typed with
+    /// the given layout, e.g. `пше ыефегы` => `git status` (for Cyrillic => QWERTY).
+    /// Characters that aren't on the layout (like spaces) are kept as is.
+    pub fn transliterate(&self, input: &str, to: Layout) -> String {
+        input
+            .chars()
+            .map(|c| {
+                // Uppercase letters were typed with shift, so they're uppercase on the other layout too.
+                let lowercase = c.to_lowercase().next().unwrap_or(c);
+                position_in(self.rows(), lowercase)
+                    .and_then(|position| key_at(to.rows(), position))
+                    .map(|key| {
+                        if lowercase != c {
+                            key.to_uppercase().next().unwrap_or(key)
+                        } else {
+                            key
+                        }
+                    })
+                    .unwrap_or(c)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ForeignLayout, Layout};
+
+    #[test]
+    fn test_are_adjacent() {
+        assert!(Layout::Qwerty.are_adjacent('g', 'h'));
+        assert!(Layout::Qwerty.are_adjacent('s', 'w'));
+        assert!(Layout::Qwerty.are_adjacent('s', 'x'));
+        assert!(Layout::Qwerty.are_adjacent('S', 'z'));
+        assert!(!Layout::Qwerty.are_adjacent('s', 'c'));
+        assert!(!Layout::Qwerty.are_adjacent('g', 'g'));
+        assert!(!Layout::Qwerty.are_adjacent('a', 'p'));
+
+        // "h" and "t" are next to each other on Dvorak's home row, but not on QWERTY.
+        assert!(Layout::Dvorak.are_adjacent('h', 't'));
+        assert!(!Layout::Qwerty.are_adjacent('h', 't'));
+        assert!(Layout::Qwertz.are_adjacent('z', 'u'));
+        assert!(Layout::Azerty.are_adjacent('a', 'z'));
+        assert!(Layout::Colemak.are_adjacent('n', 'e'));
+    }
+
+    #[test]
+    fn test_detect() {
+        assert_eq!(
+            ForeignLayout::detect("пше ыефегы"),
+            Some(ForeignLayout::Cyrillic)
+        );
+        assert_eq!(ForeignLayout::detect("γιτ"), Some(ForeignLayout::Greek));
+        assert_eq!(ForeignLayout::detect("עןא"), Some(ForeignLayout::Hebrew));
+        assert_eq!(ForeignLayout::detect("git status"), None);
+    }
+
+    #[test]
+    fn test_transliterate() {
+        assert_eq!(
+            ForeignLayout::Cyrillic.transliterate("пше ыефегы", Layout::Qwerty),
+            "git status"
+        );
+        assert_eq!(
+            ForeignLayout::Cyrillic.transliterate("пше сщььше -ь Ашч", Layout::Qwerty),
+            "git commit -m Fix"
+        );
+        assert_eq!(
+            ForeignLayout::Greek.transliterate("λσ -λα", Layout::Qwerty),
+            "ls -la"
+        );
+        assert_eq!(
+            ForeignLayout::Hebrew.transliterate("ךד -ךש", Layout::Qwerty),
+            "ls -la"
+        );
+        assert_eq!(
+            ForeignLayout::Cyrillic.transliterate("пше", Layout::Qwertz),
+            "git"
+        );
+    }
+}
@@ -1,9 +1,27 @@
-use std::{borrow::Cow, collections::HashSet};
-
+use std::{
+    borrow::Cow,
+    collections::{HashMap, HashSet},
+    ops::Range,
+    sync::{Arc, OnceLock},
+};
+
+use git::GitState;
 use itertools::Itertools;
+use keyboard::Layout;
+use risk::Risk;
+use similarity::Matcher;
 
+pub mod git;
+pub mod keyboard;
+pub mod risk;
 mod rules;
+pub mod similarity;
+mod trace;
+
+pub use trace::{CorrectionStatus, EvaluationTrace, RuleEvaluation, RuleOutcome, TracedCorrection};
 
+#[cfg(test)]
+mod proptests;
 #[cfg(test)]
 mod test_utils;
 
@@ -34,25 +52,170 @@ enum RuleCorrection<'a> {
     /// the command "mkdir -p dir && cd dir" can be expressed with an And variant like so:
     /// And(CommandParts(["mkdir", "-p", "dir"]), Command("cd dir"))
     And(Box<RuleCorrection<'a>>, Box<RuleCorrection<'a>>),
+
+    /// A command with parts the user has to fill in, e.g. `git clone <repository>`. Literal parts
+    /// are shell-escaped like in `CommandParts`. Placeholders are rendered as their (shell-escaped)
+    /// default, or as `<name>` if they don't have one.
+    /// The resulting `Correction` lists its placeholders and needs input, so clients can let the
+    /// user edit it first.
+    Template(Vec<TemplatePart<'a>>),
+
+    /// A correction along with a way to check what it would do without doing it, e.g.
+    /// `git push --force-with-lease` with the preview `git push --force-with-lease --dry-run`.
+    /// Renders as the correction; the preview ends up in `Correction::preview`.
+    WithPreview(Box<RuleCorrection<'a>>, Box<RuleCorrection<'a>>),
+}
+
+#[derive(Debug, PartialEq)]
+enum TemplatePart<'a> {
+    Literal(Cow<'a, str>),
+    Placeholder {
+        /// e.g. `repository`.
+        name: Cow<'a, str>,
+        kind: PlaceholderKind,
+        /// Our best guess for the value, if we have one.
+        default: Option<Cow<'a, str>>,
+    },
+}
+
+impl<'a> TemplatePart<'a> {
+    fn placeholder(name: impl Into<Cow<'a, str>>, kind: PlaceholderKind) -> Self {
+        TemplatePart::Placeholder {
+            name: name.into(),
+            kind,
+            default: None,
+        }
+    }
+
+    fn placeholder_with_default(
+        name: impl Into<Cow<'a, str>>,
+        kind: PlaceholderKind,
+        default: impl Into<Cow<'a, str>>,
+    ) -> Self {
+        TemplatePart::Placeholder {
+            name: name.into(),
+            kind,
+            default: Some(default.into()),
+        }
+    }
+}
+
+impl<'a, T> From<T> for TemplatePart<'a>
+where
+    T: Into<Cow<'a, str>>,
+{
+    fn from(literal: T) -> Self {
+        TemplatePart::Literal(literal.into())
+    }
 }
 
 impl<'a> RuleCorrection<'a> {
-    fn to_command_string(&self, shell: &Shell) -> String {
+    /// Returns None if the correction can't be expressed as a shell command
+    /// (e.g. one of the parts contains a nul byte).
+    fn to_command_string(&self, shell: &Shell) -> Option<String> {
+        self.render(shell).map(|(command, _)| command)
+    }
+
+    /// Like `to_command_string`, but also returns the placeholders in the command.
+    fn render(&self, shell: &Shell) -> Option<(String, Vec<Placeholder>)> {
         use RuleCorrection::*;
         match self {
             // base cases
-            Command(str) => str.to_string(),
-            CommandParts(parts) => shlex::join(parts.iter().map(|part| part.as_ref())),
+            Command(str) => Some((str.to_string(), vec![])),
+            CommandParts(parts) => {
+                let command = parts
+                    .iter()
+                    .map(|part| quote(part))
+                    .collect::<Option<Vec<_>>>()?
+                    .join(" ");
+                Some((command, vec![]))
+            }
+            Template(parts) => {
+                let mut command = String::new();
+                let mut placeholders = vec![];
+                for part in parts {
+                    if !command.is_empty() {
+                        command.push(' ');
+                    }
+                    match part {
+                        TemplatePart::Literal(literal) => command.push_str(&quote(literal)?),
+                        TemplatePart::Placeholder {
+                            name,
+                            kind,
+                            default,
+                        } => {
+                            let start = command.len();
+                            match default {
+                                Some(default) => command.push_str(&quote(default)?),
+                                None => command.push_str(&format!("<{name}>")),
+                            }
+                            placeholders.push(Placeholder {
+                                name: name.to_string(),
+                                kind: *kind,
+                                default: default.as_ref().map(|default| default.to_string()),
+                                span: start..command.len(),
+                            });
+                        }
+                    }
+                }
+                Some((command, placeholders))
+            }
 
-            // recursive case
+            // recursive cases
+            WithPreview(correction, _) => correction.render(shell),
+            And(first, second) => {
+                let (first_str, mut placeholders) = first.render(shell)?;
+                let (second_str, second_placeholders) = second.render(shell)?;
+                let command = [first_str.as_str(), shell.and(), second_str.as_str()].join(" ");
+
+                let offset = command.len() - second_str.len();
+                placeholders.extend(second_placeholders.into_iter().map(|placeholder| {
+                    Placeholder {
+                        span: placeholder.span.start + offset..placeholder.span.end + offset,
+                        ..placeholder
+                    }
+                }));
+                Some((command, placeholders))
+            }
+        }
+    }
+
+    /// The command to run to preview the correction, if it has one. Previews must not have side
+    /// effects, so `And` corrections only have one if all their parts do.
+    fn preview(&self, shell: &Shell) -> Option<String> {
+        use RuleCorrection::*;
+        match self {
+            WithPreview(_, preview) => preview.to_command_string(shell),
             And(first, second) => {
-                let first_str = first.to_command_string(shell);
-                let second_str = second.to_command_string(shell);
-                [first_str.as_str(), shell.and(), second_str.as_str()].join(" ")
+                let (first, second) = (first.preview(shell)?, second.preview(shell)?);
+                Some([first.as_str(), shell.and(), second.as_str()].join(" "))
             }
+            Command(_) | CommandParts(_) | Template(_) => None,
         }
     }
 
+    /// How dangerous it is to run the correction: the highest risk of its commands. The parts of
+    /// an `And` are classified on their own, since shells chain commands differently (e.g. fish).
+    fn risk(&self, shell: &Shell, classify: &dyn Fn(&str) -> Risk) -> Option<Risk> {
+        use RuleCorrection::*;
+        match self {
+            WithPreview(correction, _) => correction.risk(shell, classify),
+            And(first, second) => Some(
+                first
+                    .risk(shell, classify)?
+                    .max(second.risk(shell, classify)?),
+            ),
+            Command(_) | CommandParts(_) | Template(_) => {
+                Some(classify(&self.to_command_string(shell)?))
+            }
+        }
+    }
+
+    /// Adds a preview (e.g. the same command with `--dry-run`) to the correction.
+    fn with_preview(self, preview: impl Into<RuleCorrection<'a>>) -> Self {
+        RuleCorrection::WithPreview(Box::new(self), Box::new(preview.into()))
+    }
+
     /// Utility to create the And variant (without fussing with Box at callsites)
     #[allow(dead_code)]
     fn and(first: impl Into<RuleCorrection<'a>>, second: impl Into<RuleCorrection<'a>>) -> Self {
@@ -60,6 +223,19 @@ impl<'a> RuleCorrection<'a> {
     }
 }
 
+/// Shell-escapes a word like `shlex::try_quote`, but leaves options like `--name=value` as they
+/// are: `=` only needs quoting in assignments (`NAME=value`), which can't start with `-`.
+/// Returns None if the word can't be quoted (it contains a nul byte).
+fn quote(word: &str) -> Option<Cow<'_, str>> {
+    let is_plain = |part: &str| matches!(shlex::try_quote(part), Ok(Cow::Borrowed(_)));
+    if let Some((option, value)) = word.split_once('=') {
+        if word.starts_with('-') && is_plain(option) && (value.is_empty() || is_plain(value)) {
+            return Some(word.into());
+        }
+    }
+    shlex::try_quote(word).ok()
+}
+
 // Note: need two separate From impl's because we
 // can't do From<T> where T: Cow<str> (compiler will complain
 // about conflicting with the other From impl's)
@@ -126,7 +302,9 @@ impl From<usize> for ExitCode {
 }
 
 /// The shells supported by this crate.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
 pub enum Shell {
+    #[default]
     Bash,
     Zsh,
     Fish,
@@ -143,12 +321,6 @@ impl Shell {
     }
 }
 
-impl Default for Shell {
-    fn default() -> Self {
-        Shell::Bash
-    }
-}
-
 /// A Command represents a shell command that the user executed along
 /// with its metadata. This is used to determine which corrections
 /// make sense in the context of the command.
@@ -168,6 +340,10 @@ pub struct Command<'a> {
     // The following are internal, computed properties of a Command.
     lowercase_output: String,
     input_parts: Vec<String>,
+    /// The user's git aliases, read at most once (see `SessionMetadata::git_aliases`).
+    git_aliases: OnceLock<HashMap<String, String>>,
+    /// The state of the git repository, read at most once (see `SessionMetadata::git_state`).
+    git_state: OnceLock<GitState>,
 }
 
 impl<'a> Command<'a> {
@@ -185,6 +361,8 @@ impl<'a> Command<'a> {
             lowercase_output,
             input_parts,
             exit_code,
+            git_aliases: OnceLock::new(),
+            git_state: OnceLock::new(),
         }
     }
 
@@ -208,8 +386,12 @@ type ExecutableName<'a> = &'a str;
 type FunctionName<'a> = &'a str;
 type BranchName<'a> = &'a str;
 type HistoryItem<'a> = &'a str;
+type RuleId<'a> = &'a str;
+type ToolName<'a> = &'a str;
+type ContainerName<'a> = &'a str;
+type ImageName<'a> = &'a str;
 
-#[derive(Default)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
 pub enum SessionType {
     #[default]
     Local,
@@ -237,8 +419,29 @@ pub struct SessionMetadata<'a> {
     // TODO: deprecate this field once we support
     // arbitrary command execution in rules
     git_branches: HashSet<BranchName<'a>>,
+    git_state: Option<GitState>,
+    git_aliases: Option<HashMap<String, String>>,
+
+    docker_containers: HashSet<ContainerName<'a>>,
+    docker_images: HashSet<ImageName<'a>>,
+
+    /// How rules find close matches (e.g. for misspelled subcommands), unless
+    /// overridden for a specific rule in `rule_matchers`.
+    matcher: Matcher,
+    rule_matchers: HashMap<RuleId<'a>, Matcher>,
+
+    /// The (Latin) layout the user normally types with, if the client told us.
+    keyboard_layout: Option<Layout>,
+
+    tool_versions: HashMap<ToolName<'a>, &'a str>,
+
+    help_texts: HashMap<&'a str, &'a str>,
+    help_provider: Option<HelpProvider>,
 }
 
+/// Returns the `--help` output of a command (e.g. `docker compose`), if available.
+type HelpProvider = Arc<dyn Fn(&str) -> Option<String> + Send + Sync>;
+
 impl<'a> SessionMetadata<'a> {
     pub fn new() -> Self {
         Default::default()
@@ -268,6 +471,81 @@ impl<'a> SessionMetadata<'a> {
         self.git_branches = HashSet::from_iter(git_branches);
     }
 
+    /// Sets what we know about the git repository the command ran in (branches, remotes, tags, ...).
+    /// Otherwise, it's read from the repository in local sessions.
+    /// Branches from `set_git_branches` are added to the local branches.
+    pub fn set_git_state(&mut self, git_state: GitState) {
+        self.git_state = Some(git_state);
+    }
+
+    /// Sets the user's git aliases, e.g. `co` => `checkout`. Otherwise, they're read from the
+    /// user's and the repository's git config in local sessions.
+    pub fn set_git_aliases<'s>(
+        &mut self,
+        git_aliases: impl IntoIterator<Item = (&'s str, &'s str)>,
+    ) {
+        self.git_aliases = Some(
+            git_aliases
+                .into_iter()
+                .map(|(alias, expansion)| (alias.to_owned(), expansion.to_owned()))
+                .collect(),
+        );
+    }
+
+    /// The git aliases from `set_git_aliases`, or else the ones read from the user's config and
+    /// the repository the command works on (only once per command, however many rules ask).
+    fn git_aliases<'s>(&'s self, command: &'s Command) -> &'s HashMap<String, String> {
+        match &self.git_aliases {
+            Some(git_aliases) => git_aliases,
+            None => command.git_aliases.get_or_init(|| {
+                rules::GitLocation::of(command)
+                    .filter(|_| self.session_type.is_local())
+                    .map(|location| rules::read_git_aliases(&location))
+                    .unwrap_or_default()
+            }),
+        }
+    }
+
+    /// The git state from `set_git_state`, or else the one read from the repository (only once
+    /// per command, however many rules ask), with the branches from `set_git_branches`.
+    fn git_state<'s>(&'s self, command: &'s Command) -> &'s GitState {
+        command.git_state.get_or_init(|| {
+            let mut git_state = match &self.git_state {
+                Some(git_state) => git_state.clone(),
+                None => rules::GitLocation::of(command)
+                    .filter(|_| self.session_type.is_local())
+                    .and_then(|location| rules::read_git_repository(&location))
+                    .unwrap_or_default(),
+            };
+
+            let missing_branches = self
+                .git_branches
+                .iter()
+                .filter(|branch| !git_state.has_local_branch(branch))
+                .sorted()
+                .map(|branch| branch.to_string())
+                .collect_vec();
+            git_state.local_branches.extend(missing_branches);
+            git_state
+        })
+    }
+
+    /// Sets the names of the user's docker containers (e.g. from `docker ps --all --format '{{.Names}}'`),
+    /// which rules use to correct misspelled container names.
+    pub fn set_docker_containers(
+        &mut self,
+        docker_containers: impl IntoIterator<Item = ContainerName<'a>>,
+    ) {
+        self.docker_containers = HashSet::from_iter(docker_containers);
+    }
+
+    /// Sets the user's local docker images, with their tags (e.g. `nginx:1.25`, from
+    /// `docker images --format '{{.Repository}}:{{.Tag}}'`), which rules use to correct
+    /// misspelled images and unknown tags.
+    pub fn set_docker_images(&mut self, docker_images: impl IntoIterator<Item = ImageName<'a>>) {
+        self.docker_images = HashSet::from_iter(docker_images);
+    }
+
     pub fn set_session_type(&mut self, session_type: SessionType) {
         self.session_type = session_type;
     }
@@ -276,6 +554,83 @@ impl<'a> SessionMetadata<'a> {
         self.shell = shell;
     }
 
+    /// Sets how rules find close matches, e.g. `Matcher::new(JaroWinkler::default(), 0.8)`.
+    /// Defaults to difflib's ratio with a cutoff of `similarity::DEFAULT_CUTOFF`.
+    pub fn set_matcher(&mut self, matcher: Matcher) {
+        self.matcher = self.with_keyboard_layout(matcher);
+    }
+
+    /// Overrides the matcher for a single rule (by the rule's id, as in `Correction::rule_applied`).
+    pub fn set_rule_matcher(&mut self, rule_id: RuleId<'a>, matcher: Matcher) {
+        let matcher = self.with_keyboard_layout(matcher);
+        self.rule_matchers.insert(rule_id, matcher);
+    }
+
+    /// Sets the layout the user normally types with, which is used when correcting
+    /// commands typed with the wrong layout active. Matchers also use it to prefer
+    /// substitutions with adjacent keys (see `Matcher::with_layout`). Defaults to QWERTY.
+    pub fn set_keyboard_layout(&mut self, keyboard_layout: Layout) {
+        self.keyboard_layout = Some(keyboard_layout);
+        self.matcher = self.matcher.with_layout(keyboard_layout);
+        for matcher in self.rule_matchers.values_mut() {
+            *matcher = matcher.with_layout(keyboard_layout);
+        }
+    }
+
+    fn keyboard_layout(&self) -> Layout {
+        self.keyboard_layout.unwrap_or_default()
+    }
+
+    fn with_keyboard_layout(&self, matcher: Matcher) -> Matcher {
+        match self.keyboard_layout {
+            Some(keyboard_layout) => matcher.with_layout(keyboard_layout),
+            None => matcher,
+        }
+    }
+
+    /// Sets the version of a tool (e.g. `npm` => `10.2.4`), so rules only suggest subcommands
+    /// that exist in that version. The output of e.g. `docker --version` can be passed in as is.
+    pub fn set_tool_version(&mut self, tool: ToolName<'a>, version: &'a str) {
+        self.tool_versions.insert(tool, version);
+    }
+
+    fn tool_version(&self, tool: &str) -> Option<&str> {
+        self.tool_versions.get(tool).copied()
+    }
+
+    /// Sets the `--help` output of a command (e.g. `docker compose`), which rules use to learn
+    /// its subcommands and flags.
+    pub fn set_help_text(&mut self, command: &'a str, help_text: &'a str) {
+        self.help_texts.insert(command, help_text);
+    }
+
+    /// Sets a callback that returns the `--help` output of a command (e.g. by running
+    /// `docker compose --help`), for commands without help text from `set_help_text`.
+    /// The result is cached per tool version (from `set_tool_version`), or just per command if
+    /// the version isn't known.
+    pub fn set_help_provider(
+        &mut self,
+        help_provider: impl Fn(&str) -> Option<String> + Send + Sync + 'static,
+    ) {
+        self.help_provider = Some(Arc::new(help_provider));
+    }
+
+    /// The subcommands and flags of a command (e.g. `docker compose`), learned from its help text.
+    fn help(&self, command: &str) -> Option<Arc<rules::ParsedHelp>> {
+        if let Some(help_text) = self.help_texts.get(command) {
+            return Some(Arc::new(rules::parse_help(help_text)));
+        }
+
+        let help_provider = self.help_provider.as_ref()?;
+        let tool = command.split_whitespace().next()?;
+        rules::parse_help_cached(command, self.tool_version(tool), || help_provider(command))
+    }
+
+    /// The matcher the rule with the given id should use.
+    fn matcher(&self, rule_id: &str) -> &Matcher {
+        self.rule_matchers.get(rule_id).unwrap_or(&self.matcher)
+    }
+
     fn is_top_level_command(&self, command: &str) -> bool {
         self.executables.contains(command)
             || self.aliases.contains(command)
@@ -307,54 +662,185 @@ impl<'a> SessionMetadata<'a> {
 pub struct Correction {
     pub command: String,
     pub rule_applied: &'static str,
+    /// Parts of the command the user has to fill in (e.g. `<repository>` in `git clone <repository>`).
+    pub placeholders: Vec<Placeholder>,
+    /// How dangerous it is to run the correction, from the rule and the corrected command.
+    /// Clients may want to only run read-only or local corrections without asking.
+    pub risk: Risk,
+    /// A command that shows what the correction would do without doing it (e.g. with
+    /// `--dry-run`), for corrections with side effects.
+    pub preview: Option<String>,
+}
+
+impl Correction {
+    /// Whether the user has to fill in (or at least check) some placeholders before running the
+    /// command. Clients should put such corrections in the line editor, with the cursor on the
+    /// first placeholder, instead of running them.
+    pub fn needs_input(&self) -> bool {
+        !self.placeholders.is_empty()
+    }
+}
+
+/// A part of a correction the user has to fill in (or at least check).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Placeholder {
+    /// What should go here, e.g. `repository`. Placeholders with the same name stand for the
+    /// same value, so clients should edit them together.
+    pub name: String,
+    pub kind: PlaceholderKind,
+    /// The value in the command, if we could guess one. Otherwise the command has `<name>`.
+    pub default: Option<String>,
+    /// The byte range of the placeholder (e.g. `<repository>`) in `Correction::command`.
+    pub span: Range<usize>,
+}
+
+/// What kind of value a placeholder takes, e.g. for clients to offer completions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlaceholderKind {
+    Argument,
+    Path,
+    Url,
+    Branch,
+    Remote,
+    Registry,
+    Message,
 }
 
 /// Returns a list of command corrections given a command. This is _heavily_ inspired
 /// by The Fuck (https://github.com/nvbn/thefuck).
 pub fn correct_command(command: Command, session_metadata: &SessionMetadata) -> Vec<Correction> {
-    let rules_by_command = &*rules::RULES_BY_COMMAND;
+    apply_rules(
+        rules_for_command(&command),
+        &command,
+        session_metadata,
+        None,
+    )
+}
 
-    let command_name = match command.input_parts.first() {
-        None => return vec![],
-        Some(first) => first,
-    };
+/// Same as `correct_command`, but also returns a log of how each rule was evaluated.
+/// Useful for debugging why an expected correction doesn't show up.
+pub fn correct_command_traced(
+    command: Command,
+    session_metadata: &SessionMetadata,
+) -> (Vec<Correction>, EvaluationTrace) {
+    let mut trace = EvaluationTrace::new(command.input);
+    let corrections = apply_rules(
+        rules_for_command(&command),
+        &command,
+        session_metadata,
+        Some(&mut trace),
+    );
+    (corrections, trace)
+}
+
+/// The rules that could apply to the command, in the order they should be evaluated.
+fn rules_for_command(command: &Command) -> impl Iterator<Item = &'static Arc<dyn rules::Rule>> {
+    let rules_by_command = &*rules::RULES_BY_COMMAND;
 
     // Note: the current order that the rules are applied in is:
     // 1. command specific rules before generic rules
     // 2. within each rule group, the order of rules is the order in which they're evaluated
     // This is a stopgap until we support `Priority` on a RuleCorrection
-    rules_by_command
-        .get(command_name)
+    let rules = command.input_parts.first().map(|command_name| {
+        rules_by_command
+            .get(command_name)
+            .into_iter()
+            .flatten()
+            .chain(rules::GENERIC_RULES.iter())
+    });
+    // If there's no command name, there's nothing to correct (so no rules apply).
+    rules.into_iter().flatten()
+}
+
+pub(crate) fn apply_rules<'r>(
+    rules: impl Iterator<Item = &'r Arc<dyn rules::Rule>>,
+    command: &Command,
+    session_metadata: &SessionMetadata,
+    mut trace: Option<&mut EvaluationTrace>,
+) -> Vec<Correction> {
+    let mut corrections = vec![];
+    // Maps each suggested command to the rule that first suggested it.
+    let mut suggested_by: HashMap<String, &'static str> = HashMap::new();
+    let classify = |cmd_string: &str| {
+        risk::classify_with_git_aliases(cmd_string, &|| session_metadata.git_aliases(command))
+    };
+
+    for rule in rules {
+        // Only check a rule if it should be considered by default. And finally, make sure
+        // the rule matches. Note: the order of these is important. `matches` can be expensive
+        // so we check it last.
+        let outcome = if !rule.should_be_considered_by_default(command, session_metadata) {
+            RuleOutcome::NotConsidered
+        } else if !rule.matches(command, session_metadata) {
+            RuleOutcome::NotMatched
+        } else {
+            match rule.generate_command_corrections(command, session_metadata) {
+                None => RuleOutcome::NoCorrections,
+                Some(rule_corrections) => RuleOutcome::Corrected(
+                    rule_corrections
+                        .into_iter()
+                        .map(|rule_correction| {
+                            let rendered = rule_correction.render(&session_metadata.shell);
+                            let Some((cmd_string, placeholders)) = rendered else {
+                                return TracedCorrection {
+                                    command: format!("{rule_correction:?}"),
+                                    status: CorrectionStatus::Unrepresentable,
+                                };
+                            };
+                            let (cmd_string, placeholders) = trim(cmd_string, placeholders);
+
+                            // Don't consider corrections that look exactly like the original command input,
+                            // or that an earlier rule already suggested.
+                            let status = if cmd_string == command.input {
+                                CorrectionStatus::SameAsInput
+                            } else if let Some(rule_id) = suggested_by.get(&cmd_string) {
+                                CorrectionStatus::Duplicate(rule_id)
+                            } else {
+                                suggested_by.insert(cmd_string.clone(), rule.id());
+                                corrections.push(Correction {
+                                    command: cmd_string.clone(),
+                                    rule_applied: rule.id(),
+                                    risk: rule.risk().max(
+                                        rule_correction
+                                            .risk(&session_metadata.shell, &classify)
+                                            .unwrap_or_else(|| classify(&cmd_string)),
+                                    ),
+                                    preview: rule_correction
+                                        .preview(&session_metadata.shell)
+                                        .map(|preview| preview.trim().to_owned()),
+                                    placeholders,
+                                });
+                                CorrectionStatus::Accepted
+                            };
+
+                            TracedCorrection {
+                                command: cmd_string,
+                                status,
+                            }
+                        })
+                        .collect(),
+                ),
+            }
+        };
+
+        if let Some(trace) = trace.as_deref_mut() {
+            trace.record(rule.id(), outcome);
+        }
+    }
+
+    corrections
+}
+
+/// Trims the command, keeping the placeholder spans in sync.
+fn trim(command: String, placeholders: Vec<Placeholder>) -> (String, Vec<Placeholder>) {
+    let trimmed = command.trim_start();
+    let offset = command.len() - trimmed.len();
+    let placeholders = placeholders
         .into_iter()
-        .flatten()
-        .chain(rules::GENERIC_RULES.iter())
-        .filter(|rule| {
-            // Only check a rule if it should be considered by default.
-            let should_be_considered =
-                rule.should_be_considered_by_default(&command, session_metadata);
-
-            // And finally, make sure the rule matches. Note: the order of these is important.
-            // `matches` can be expensive so we check it last.
-            should_be_considered && rule.matches(&command, session_metadata)
-        })
-        .flat_map(|rule| {
-            // Generate the corrections for this rule.
-            rule.generate_command_corrections(&command, session_metadata)
-                .into_iter()
-                .flatten()
-                .filter_map(|rule_correction| {
-                    // Don't consider corrections that look exactly like the original command input.
-                    let cmd_string = rule_correction
-                        .to_command_string(&session_metadata.shell)
-                        .trim()
-                        .to_owned();
-
-                    (cmd_string != command.input).then_some(Correction {
-                        command: cmd_string,
-                        rule_applied: rule.id(),
-                    })
-                })
+        .map(|placeholder| Placeholder {
+            span: placeholder.span.start - offset..placeholder.span.end - offset,
+            ..placeholder
         })
-        .unique_by(|correction| correction.command.to_owned())
-        .collect()
+        .collect();
+    (trimmed.trim_end().to_owned(), placeholders)
 }
@@ -0,0 +1,204 @@
+//! Property tests for the correction pipeline. These feed arbitrary (and arbitrary-but-plausible)
+//! commands through every rule to make sure rules don't assume well-formed input.
+
+use proptest::prelude::*;
+
+use crate::{
+    correct_command, rules_for_command, Command, ExitCode, RuleCorrection, SessionMetadata,
+    SessionType, Shell,
+};
+
+const EXECUTABLES: &[&str] = &[
+    "git", "cargo", "docker", "brew", "npm", "yarn", "cd", "ls", "cp", "sed", "python", "pip",
+];
+const GIT_BRANCHES: &[&str] = &["main", "master", "develop", "feature/login"];
+const HISTORY: &[&str] = &["git status", "cargo build", "docker ps", "cd src"];
+
+/// Snippets of real error output that make rules match, so that the interesting code paths
+/// (`generate_command_corrections`) actually get exercised.
+const OUTPUT_SNIPPETS: &[&str] = &[
+    "command not found",
+    "zsh: command not found: $",
+    "permission denied",
+    "is a directory",
+    "omitting directory",
+    "no such file or directory",
+    "does not exist",
+    "unterminated",
+    "unknown command",
+    "Unknown command: \"",
+    "Command \"",
+    "\" not found. Did you mean \"",
+    "is not a git command",
+    "The most similar commands are\n",
+    "Did you mean\n",
+    "error: pathspec '",
+    "' did not match any file(s) known to git",
+    "git push --set-upstream ",
+    "! [rejected]",
+    "failed to push some refs",
+    "updates were rejected because the tip of your current branch is behind",
+    "' is not a docker command",
+    "access denied",
+    "No available formula with the name \"",
+    "These similarly named formulae were found:\n",
+    "To install",
+    "brew link --overwrite --dry-run ",
+    "no such subcommand",
+    "Did you mean `",
+    "ModuleNotFoundError: No module named '",
+    "The file ",
+    "Run \"",
+    "\" instead",
+    "Visit ",
+    " for ",
+];
+
+fn session_metadata(shell: Shell, session_type: SessionType) -> SessionMetadata<'static> {
+    let mut metadata = SessionMetadata::new();
+    metadata.set_executables(EXECUTABLES.iter().copied());
+    metadata.set_git_branches(GIT_BRANCHES.iter().copied());
+    metadata.set_history(HISTORY.iter().copied());
+    metadata.set_shell(shell);
+    metadata.set_session_type(session_type);
+    metadata
+}
+
+fn shell() -> impl Strategy<Value = Shell> {
+    prop_oneof![Just(Shell::Bash), Just(Shell::Zsh), Just(Shell::Fish)]
+}
+
+fn session_type() -> impl Strategy<Value = SessionType> {
+    prop_oneof![Just(SessionType::Local), Just(SessionType::Remote)]
+}
+
+fn exit_code() -> impl Strategy<Value = usize> {
+    prop_oneof![
+        Just(0),
+        Just(1),
+        Just(126),
+        Just(127),
+        Just(128),
+        any::<usize>()
+    ]
+}
+
+/// A word that's likely to mean something to a rule (a command, a flag, a quote, ...) or is garbage.
+fn word() -> impl Strategy<Value = String> {
+    prop_oneof![
+        proptest::sample::select(EXECUTABLES).prop_map(str::to_owned),
+        proptest::sample::select(GIT_BRANCHES).prop_map(str::to_owned),
+        proptest::sample::select(
+            &[
+                "$",
+                "-",
+                "--",
+                "-u",
+                "--set-upstream",
+                "--name=web",
+                "--name=a b",
+                "checkout",
+                "push",
+                "image",
+                "rm",
+                "install",
+                "update",
+                "link",
+                "help",
+                "s/a/b",
+                "-es/a",
+                "./foo",
+                "./foo.py",
+                "Foo.java",
+                "..",
+                "cd..",
+                "\"",
+                "'",
+                "\\",
+                "&&",
+                "",
+            ][..]
+        )
+        .prop_map(str::to_owned),
+        "\\PC{0,12}",
+        any::<String>(),
+    ]
+}
+
+fn input() -> impl Strategy<Value = String> {
+    prop_oneof![
+        (
+            proptest::sample::select(EXECUTABLES),
+            proptest::collection::vec(word(), 0..6)
+        )
+            .prop_map(|(command_name, words)| format!("{command_name} {}", words.join(" "))),
+        proptest::collection::vec(word(), 0..6).prop_map(|words| words.join(" ")),
+        any::<String>(),
+    ]
+}
+
+fn output() -> impl Strategy<Value = String> {
+    prop_oneof![
+        proptest::collection::vec(
+            prop_oneof![
+                proptest::sample::select(OUTPUT_SNIPPETS).prop_map(str::to_owned),
+                word(),
+                Just("\n".to_owned()),
+            ],
+            0..8
+        )
+        .prop_map(|parts| parts.concat()),
+        any::<String>(),
+    ]
+}
+
+proptest! {
+    #[test]
+    fn test_correct_command_never_suggests_the_input(
+        input in input(),
+        output in output(),
+        exit_code in exit_code(),
+        shell in shell(),
+        session_type in session_type(),
+    ) {
+        let metadata = session_metadata(shell, session_type);
+        let command = Command::new(&input, &output, ExitCode(exit_code));
+        let trimmed_input = command.input;
+
+        for correction in correct_command(command, &metadata) {
+            prop_assert_ne!(correction.command.as_str(), trimmed_input);
+        }
+    }
+
+    #[test]
+    fn test_command_parts_round_trip(
+        input in input(),
+        output in output(),
+        exit_code in exit_code(),
+        session_type in session_type(),
+    ) {
+        let metadata = session_metadata(Shell::Bash, session_type);
+        let command = Command::new(&input, &output, ExitCode(exit_code));
+
+        for rule in rules_for_command(&command) {
+            if !(rule.should_be_considered_by_default(&command, &metadata)
+                && rule.matches(&command, &metadata))
+            {
+                continue;
+            }
+
+            for correction in rule.generate_command_corrections(&command, &metadata).into_iter().flatten() {
+                if let RuleCorrection::CommandParts(parts) = &correction {
+                    // Corrections that can't be rendered (nul bytes) are dropped by the pipeline.
+                    if let Some(cmd_string) = correction.to_command_string(&metadata.shell) {
+                        prop_assert_eq!(
+                            shlex::split(&cmd_string),
+                            Some(parts.iter().map(|part| part.to_string()).collect()),
+                            "rule {} produced {:?}", rule.id(), cmd_string
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,393 @@
+//! How dangerous it is to run a correction, so clients can refuse to run risky
+//! corrections without asking (see `Correction::risk`).
+
+use std::collections::HashMap;
+
+use crate::rules::resolve_git_alias;
+
+/// The side effects a command may have, from least to most dangerous.
+/// Risks are ordered, so the risk of several commands is the `max` of their risks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Risk {
+    /// Only reads state, e.g. `git status` or `ls`.
+    ReadOnly,
+    /// Changes local state that's easy to get back, e.g. `git commit` or `mkdir`.
+    /// Commands we don't know about are assumed to be this.
+    ModifiesLocalState,
+    /// Changes state elsewhere, e.g. `git push`, `docker push` or `npm publish`.
+    NetworkPush,
+    /// May lose data, e.g. `rm`, `git reset --hard` or `git push --force`.
+    Destructive,
+    /// Runs with elevated privileges, e.g. `sudo`.
+    PrivilegeEscalation,
+}
+
+// Fish chains commands with `and` and `or` (and negates them with `not`). In other shells,
+// that's at worst an overestimate for an argument named `and`.
+const SHELL_OPERATORS: &[&str] = &["&&", "||", ";", "|", "&", "and", "or"];
+
+// Commands that run another command, which is what we classify.
+const WRAPPERS: &[&str] = &[
+    "command", "env", "exec", "nice", "nohup", "not", "time", "xargs",
+];
+const PRIVILEGE_ESCALATION: &[&str] = &["sudo", "doas", "su", "pkexec", "runas"];
+
+const READ_ONLY: &[&str] = &[
+    "cat", "cd", "date", "df", "diff", "du", "echo", "file", "grep", "head", "history", "less",
+    "ls", "man", "more", "ps", "pwd", "rg", "stat", "tail", "top", "tree", "type", "wc", "which",
+    "whoami",
+];
+const DESTRUCTIVE: &[&str] = &[
+    "dd", "kill", "killall", "mkfs", "pkill", "rm", "rmdir", "shred", "truncate", "unlink",
+];
+
+// Returns the user's git aliases, which are only read if a git command needs them.
+type GitAliases<'h> = &'h dyn Fn() -> &'h HashMap<String, String>;
+
+/// Classifies a shell command. Chained commands (`a && b`, `a | b`, `a; and b`, ...) get the
+/// highest risk of their parts. This is a heuristic: it knows the common commands of a few tools
+/// (git, docker, brew, package managers, kubectl, helm) and assumes anything else modifies local
+/// state.
+pub fn classify(command: &str) -> Risk {
+    let no_aliases = HashMap::new();
+    classify_with_git_aliases(command, &|| &no_aliases)
+}
+
+/// Same as `classify`, but git aliases (e.g. `p = push`) are classified as what they run.
+pub(crate) fn classify_with_git_aliases(command: &str, git_aliases: GitAliases) -> Risk {
+    let words = shlex::split(command)
+        .unwrap_or_else(|| command.split_whitespace().map(str::to_owned).collect());
+
+    // `a; b` is split as `a;` and `b`.
+    let words = words.iter().flat_map(|word| match word.strip_suffix(';') {
+        Some(word) if !word.is_empty() => vec![word, ";"],
+        _ => vec![word.as_str()],
+    });
+
+    let mut risk = Risk::ReadOnly;
+    let mut simple_command = vec![];
+    for word in words.chain([";"]) {
+        if SHELL_OPERATORS.contains(&word) {
+            risk = risk.max(classify_simple_command(&simple_command, git_aliases));
+            simple_command.clear();
+        } else {
+            simple_command.push(word);
+        }
+    }
+    risk
+}
+
+/// Classifies a command without shell operators, e.g. `git push --force`.
+fn classify_simple_command(words: &[&str], git_aliases: GitAliases) -> Risk {
+    // Skip environment variables, e.g. `FOO=bar cmd`.
+    let words = match words.iter().position(|word| !is_assignment(word)) {
+        Some(start) => &words[start..],
+        None => return Risk::ReadOnly,
+    };
+    let Some((program, args)) = words.split_first() else {
+        return Risk::ReadOnly;
+    };
+    let program = program.rsplit('/').next().unwrap_or(program);
+
+    if PRIVILEGE_ESCALATION.contains(&program) {
+        return Risk::PrivilegeEscalation;
+    }
+    if WRAPPERS.contains(&program) {
+        let start = args
+            .iter()
+            .position(|arg| !arg.starts_with('-') && !is_assignment(arg))
+            .unwrap_or(args.len());
+        return classify_simple_command(&args[start..], git_aliases);
+    }
+
+    // Writing to a file, e.g. `echo foo > file`.
+    let redirects = args
+        .iter()
+        .any(|arg| arg.starts_with('>') || arg.starts_with("1>") || arg.starts_with("&>"));
+    let risk = match program {
+        "git" => classify_git(args, git_aliases),
+        "docker" | "podman" | "docker-compose" | "podman-compose" => classify_docker(args),
+        "brew" => classify_brew(args),
+        "npm" | "yarn" | "pnpm" | "cargo" => classify_package_manager(args),
+        "kubectl" | "helm" => classify_kubernetes(args),
+        "find" if has_any(args, &["-delete", "rm"]) => Risk::Destructive,
+        "find" => Risk::ReadOnly,
+        "mv" | "cp" if has_any(args, &["-f", "--force"]) => Risk::Destructive,
+        _ if READ_ONLY.contains(&program) => Risk::ReadOnly,
+        _ if DESTRUCTIVE.contains(&program) || program.starts_with("mkfs.") => Risk::Destructive,
+        _ => Risk::ModifiesLocalState,
+    };
+    if redirects {
+        risk.max(Risk::ModifiesLocalState)
+    } else {
+        risk
+    }
+}
+
+fn classify_git(args: &[&str], git_aliases: GitAliases) -> Risk {
+    // Skip global options, e.g. `git -C dir push`.
+    let mut args = args;
+    while let Some((first, rest)) = args.split_first() {
+        if !first.starts_with('-') {
+            break;
+        }
+        args = match *first {
+            "-C" | "-c" | "--git-dir" | "--work-tree" | "--namespace" => {
+                rest.get(1..).unwrap_or(&[])
+            }
+            _ => rest,
+        };
+    }
+    let Some((typed, args)) = args.split_first() else {
+        return Risk::ReadOnly;
+    };
+    let (subcommand, alias_args) = resolve_git_alias(typed, git_aliases);
+    let args = alias_args
+        .iter()
+        .map(String::as_str)
+        .chain(args.iter().copied())
+        .collect::<Vec<_>>();
+    let args = args.as_slice();
+
+    match subcommand.as_str() {
+        "push" => {
+            let force = has_any(
+                args,
+                &["-f", "--force", "--force-with-lease", "--delete", "-d"],
+            ) || args
+                .iter()
+                .any(|arg| arg.starts_with("--force") || arg.starts_with(['+', ':']));
+            if force {
+                Risk::Destructive
+            } else {
+                Risk::NetworkPush
+            }
+        }
+        "reset" if has_any(args, &["--hard", "--merge", "--keep"]) => Risk::Destructive,
+        "clean" if !has_any(args, &["-n", "--dry-run"]) => Risk::Destructive,
+        "checkout" if has_any(args, &["-f", "--force", "-B", "--", "."]) => Risk::Destructive,
+        "switch"
+            if has_any(
+                args,
+                &["-f", "--force", "-C", "--force-create", "--discard-changes"],
+            ) =>
+        {
+            Risk::Destructive
+        }
+        "restore" if !has_any(args, &["--staged", "-S"]) => Risk::Destructive,
+        "branch" if has_any(args, &["-D", "--delete", "-d", "-M", "-f", "--force"]) => {
+            Risk::Destructive
+        }
+        "tag" if has_any(args, &["-d", "--delete", "-f", "--force"]) => Risk::Destructive,
+        "stash" if has_any(args, &["drop", "clear"]) => Risk::Destructive,
+        // Aborting throws away the conflict resolution done so far.
+        "merge" | "rebase" | "cherry-pick" | "revert" | "am" if has_any(args, &["--abort"]) => {
+            Risk::Destructive
+        }
+        "stash" if has_any(args, &["list", "show"]) => Risk::ReadOnly,
+        "branch" | "tag" | "remote" | "config" | "worktree" if is_listing(args) => Risk::ReadOnly,
+        "blame" | "describe" | "diff" | "grep" | "help" | "log" | "ls-files" | "ls-remote"
+        | "reflog" | "rev-parse" | "shortlog" | "show" | "status" | "version" => Risk::ReadOnly,
+        _ => Risk::ModifiesLocalState,
+    }
+}
+
+fn classify_docker(args: &[&str]) -> Risk {
+    let args = args
+        .iter()
+        .copied()
+        .filter(|arg| !arg.starts_with('-'))
+        .take(2)
+        .collect::<Vec<_>>();
+    // Management commands, e.g. `docker image rm` or `docker container ls`.
+    let action = match args.as_slice() {
+        ["image" | "container" | "network" | "volume" | "system" | "builder" | "buildx"
+        | "compose" | "pod", action] => *action,
+        [action, ..] => *action,
+        [] => return Risk::ReadOnly,
+    };
+
+    match action {
+        "rm" | "rmi" | "kill" | "prune" | "remove" => Risk::Destructive,
+        "push" => Risk::NetworkPush,
+        "df" | "events" | "history" | "images" | "info" | "inspect" | "logs" | "ls" | "ps"
+        | "search" | "stats" | "top" | "version" => Risk::ReadOnly,
+        _ => Risk::ModifiesLocalState,
+    }
+}
+
+fn classify_brew(args: &[&str]) -> Risk {
+    match args.iter().find(|arg| !arg.starts_with('-')) {
+        Some(&("uninstall" | "remove" | "rm" | "cleanup" | "untap" | "autoremove")) => {
+            Risk::Destructive
+        }
+        Some(
+            &("config" | "deps" | "doctor" | "home" | "info" | "leaves" | "list" | "ls"
+            | "outdated" | "search" | "uses"),
+        ) => Risk::ReadOnly,
+        None if has_any(args, &["--version", "-v", "--help"]) => Risk::ReadOnly,
+        _ => Risk::ModifiesLocalState,
+    }
+}
+
+fn classify_package_manager(args: &[&str]) -> Risk {
+    match args.iter().find(|arg| !arg.starts_with('-')) {
+        Some(&("publish" | "unpublish" | "yank" | "deprecate")) => Risk::NetworkPush,
+        Some(&("list" | "ls" | "outdated" | "search" | "tree" | "view" | "info" | "why")) => {
+            Risk::ReadOnly
+        }
+        _ => Risk::ModifiesLocalState,
+    }
+}
+
+fn classify_kubernetes(args: &[&str]) -> Risk {
+    match args.iter().find(|arg| !arg.starts_with('-')) {
+        Some(&("delete" | "drain" | "uninstall")) => Risk::Destructive,
+        Some(
+            &("api-resources" | "cluster-info" | "describe" | "diff" | "explain" | "get"
+            | "history" | "lint" | "list" | "logs" | "search" | "show" | "status" | "template"
+            | "top" | "version"),
+        ) => Risk::ReadOnly,
+        // Anything else changes the cluster (`apply`, `scale`, `install`, ...).
+        _ => Risk::NetworkPush,
+    }
+}
+
+fn has_any(args: &[&str], options: &[&str]) -> bool {
+    args.iter().any(|arg| options.contains(arg))
+}
+
+/// e.g. `git branch`, `git tag -l` or `git remote -v`.
+fn is_listing(args: &[&str]) -> bool {
+    args.iter().all(|arg| {
+        [
+            "-l",
+            "--list",
+            "-v",
+            "-vv",
+            "--verbose",
+            "-a",
+            "--all",
+            "-r",
+            "--get",
+            "show",
+        ]
+        .contains(arg)
+    })
+}
+
+fn is_assignment(word: &str) -> bool {
+    word.split_once('=').is_some_and(|(name, _)| {
+        !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::{classify, classify_with_git_aliases, Risk};
+    use crate::{correct_command, Command, ExitCode, SessionMetadata, Shell};
+
+    #[test]
+    fn test_classify() {
+        let cases = [
+            ("git status", Risk::ReadOnly),
+            ("git -C repo log --oneline", Risk::ReadOnly),
+            ("git branch", Risk::ReadOnly),
+            ("git commit -m 'fix'", Risk::ModifiesLocalState),
+            ("git push --set-upstream origin main", Risk::NetworkPush),
+            ("git push --force-with-lease", Risk::Destructive),
+            ("git push origin +main", Risk::Destructive),
+            ("git reset --hard HEAD~1", Risk::Destructive),
+            ("git branch -D feature", Risk::Destructive),
+            ("git rebase --abort", Risk::Destructive),
+            ("docker ps -a", Risk::ReadOnly),
+            ("docker image rm --force ssh_image", Risk::Destructive),
+            ("docker push ghcr.io/owner/app", Risk::NetworkPush),
+            ("docker compose rm -f", Risk::Destructive),
+            ("podman-compose ps", Risk::ReadOnly),
+            ("brew uninstall --force jq", Risk::Destructive),
+            ("brew install jq", Risk::ModifiesLocalState),
+            ("cargo publish", Risk::NetworkPush),
+            ("kubectl get pods", Risk::ReadOnly),
+            ("kubectl apply -f deploy.yaml", Risk::NetworkPush),
+            ("rm -rf build", Risk::Destructive),
+            ("find . -name '*.o' -delete", Risk::Destructive),
+            ("ls -la", Risk::ReadOnly),
+            ("echo hi > file", Risk::ModifiesLocalState),
+            ("mkdir foo", Risk::ModifiesLocalState),
+            ("some-unknown-tool", Risk::ModifiesLocalState),
+        ];
+        for (command, risk) in cases {
+            assert_eq!(classify(command), risk, "{command}");
+        }
+    }
+
+    #[test]
+    fn test_classify_chains_and_wrappers() {
+        let cases = [
+            ("sudo apt install jq", Risk::PrivilegeEscalation),
+            ("/usr/bin/sudo ls", Risk::PrivilegeEscalation),
+            ("mkdir -p dir && cd dir", Risk::ModifiesLocalState),
+            ("git status; git push", Risk::NetworkPush),
+            ("ls | xargs rm", Risk::Destructive),
+            ("FOO=bar env -i git log", Risk::ReadOnly),
+            ("docker login && docker push repo/image", Risk::NetworkPush),
+            // fish
+            ("docker login and docker push repo/image", Risk::NetworkPush),
+            ("git status; and rm -rf /", Risk::Destructive),
+            ("git status and rm -rf /", Risk::Destructive),
+            ("not git diff --quiet; or git push", Risk::NetworkPush),
+        ];
+        for (command, risk) in cases {
+            assert_eq!(classify(command), risk, "{command}");
+        }
+    }
+
+    #[test]
+    fn test_classify_git_aliases() {
+        let aliases = HashMap::from([
+            ("p".to_owned(), "push".to_owned()),
+            ("co".to_owned(), "checkout".to_owned()),
+            ("nuke".to_owned(), "reset --hard".to_owned()),
+        ]);
+        let classify = |command| classify_with_git_aliases(command, &|| &aliases);
+        assert_eq!(
+            classify("git -C app p --set-upstream origin random"),
+            Risk::NetworkPush
+        );
+        assert_eq!(classify("git co -B x"), Risk::Destructive);
+        assert_eq!(classify("git nuke"), Risk::Destructive);
+        assert_eq!(classify("git status"), Risk::ReadOnly);
+    }
+
+    #[test]
+    fn test_correction_risk() {
+        let command = Command::new(
+            "touch /etc/hosts",
+            "touch: /etc/hosts: Permission denied",
+            ExitCode(1),
+        );
+        let corrections = correct_command(command, &SessionMetadata::new());
+        assert_eq!(corrections[0].command, "sudo touch /etc/hosts");
+        assert_eq!(corrections[0].risk, Risk::PrivilegeEscalation);
+    }
+
+    #[test]
+    fn test_correction_risk_in_fish() {
+        let command = Command::new(
+            "docker push repo/image",
+            "push access denied for repo/image, repository does not exist or may require 'docker login'",
+            ExitCode(1),
+        );
+        let mut session_metadata = SessionMetadata::new();
+        session_metadata.set_shell(Shell::Fish);
+        let corrections = correct_command(command, &session_metadata);
+        assert_eq!(
+            corrections[0].command,
+            "docker login and docker push repo/image"
+        );
+        assert_eq!(corrections[0].risk, Risk::NetworkPush);
+    }
+}
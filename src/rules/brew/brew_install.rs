@@ -1,4 +1,4 @@
-use crate::rules::util::new_commands_from_suggestions;
+use crate::rules::util::{new_commands_from_suggestions, with_flag_previews};
 use crate::rules::Rule;
 use crate::{default_rule_id, Command, RuleCorrection, SessionMetadata};
 use lazy_static::lazy_static;
@@ -44,7 +44,9 @@ impl Rule for BrewInstall {
                     .collect::<Vec<&str>>()
             })?;
 
-        new_commands_from_suggestions(corrected_formulae, command.input_parts(), to_fix)
+        let corrections =
+            new_commands_from_suggestions(corrected_formulae, command.input_parts(), to_fix)?;
+        Some(with_flag_previews(corrections, "install", "--dry-run"))
     }
 }
 
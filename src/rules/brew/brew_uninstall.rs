@@ -1,3 +1,4 @@
+use crate::risk::Risk;
 use crate::rules::Rule;
 use crate::{default_rule_id, Command, RuleCorrection, SessionMetadata};
 
@@ -29,6 +30,11 @@ impl Rule for BrewUninstall {
         replacement.insert(uninstall_pos + 1, "--force".to_owned());
         Some(vec![replacement.into()])
     }
+
+    /// Forcing the uninstall removes every installed version.
+    fn risk(&self) -> Risk {
+        Risk::Destructive
+    }
 }
 
 #[cfg(test)]
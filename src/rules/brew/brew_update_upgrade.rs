@@ -1,4 +1,4 @@
-use crate::rules::util::new_commands_from_suggestions;
+use crate::rules::util::{new_commands_from_suggestions, with_flag_previews};
 use crate::rules::Rule;
 use crate::{default_rule_id, Command, RuleCorrection, SessionMetadata};
 
@@ -19,13 +19,15 @@ impl Rule for BrewUpdateUpgrade {
         command: &'a Command,
         _session_metadata: &'a SessionMetadata,
     ) -> Option<Vec<RuleCorrection<'a>>> {
-        new_commands_from_suggestions(["upgrade"], command.input_parts(), "update")
+        let corrections =
+            new_commands_from_suggestions(["upgrade"], command.input_parts(), "update")?;
+        Some(with_flag_previews(corrections, "upgrade", "--dry-run"))
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::test_utils::basic_corrections;
+    use crate::test_utils::{basic_corrections, basic_previews};
 
     #[test]
     fn test_brew_update_upgrade() {
@@ -38,4 +40,15 @@ mod tests {
             vec!["brew upgrade jq"]
         )
     }
+
+    #[test]
+    fn test_brew_update_upgrade_preview() {
+        assert_eq!(
+            basic_previews(
+                "brew update jq",
+                "Error: This command updates brew itself, and does not take formula names."
+            ),
+            vec![Some("brew upgrade --dry-run jq".to_owned())]
+        )
+    }
 }
@@ -51,7 +51,7 @@ mod tests {
     fn test_cat_dir_with_spaces() {
         assert_eq!(
             basic_corrections("cat foo\\ bar", "cat: foo bar: Is a directory"),
-            vec![r#"ls "foo bar""#]
+            vec!["ls 'foo bar'"]
         )
     }
 }
@@ -0,0 +1,1340 @@
+//! What we know about the subcommands and flags of common tools. Rules that need to know
+//! whether a word is a subcommand (e.g. `UnknownSubcommand`) should look it up here, so
+//! supporting a new tool is just a matter of adding it to `TOOLS`.
+
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use crate::SessionMetadata;
+
+lazy_static! {
+    static ref VERSION_RE: Regex = Regex::new(r"(\d+)\.(\d+)(?:\.(\d+))?").unwrap();
+}
+
+/// A tool (e.g. `docker`) along with how it reports unknown subcommands.
+pub(crate) struct Tool {
+    /// The command names the tool goes by.
+    pub names: &'static [&'static str],
+    /// The id of the tool's `UnknownSubcommand` rule, or `None` if the tool's unknown
+    /// subcommands are corrected by a dedicated rule (e.g. `GitCommandNotFound`).
+    pub rule_id: Option<&'static str>,
+    /// Patterns for the tool's "unknown subcommand" errors. The first capture group is the
+    /// unknown subcommand; only its last word is used (e.g. `compose upp` => `upp`).
+    pub unknown_subcommand_errors: &'static [&'static str],
+    pub command: CommandSpec,
+}
+
+/// A command (or subcommand) and what can follow it.
+pub(crate) struct CommandSpec {
+    pub name: &'static str,
+    /// Subcommands that don't have subcommands of their own.
+    pub subcommands: &'static [&'static str],
+    /// Subcommands that have subcommands or flags of their own (e.g. `docker compose`).
+    pub groups: &'static [CommandSpec],
+    pub flags: &'static [&'static str],
+    /// The subcommands (from either `subcommands` or `groups`) that only exist in some versions.
+    pub versioned: &'static [(&'static str, Versions)],
+}
+
+const EMPTY: CommandSpec = CommandSpec {
+    name: "",
+    subcommands: &[],
+    groups: &[],
+    flags: &[],
+    versioned: &[],
+};
+
+impl CommandSpec {
+    /// The names of all subcommands that exist in the given version of the tool
+    /// (or in any version, if we don't know it).
+    pub fn subcommand_names(
+        &self,
+        version: Option<Version>,
+    ) -> impl Iterator<Item = &'static str> + '_ {
+        self.subcommands
+            .iter()
+            .copied()
+            .chain(self.groups.iter().map(|group| group.name))
+            .filter(move |name| self.is_available(name, version))
+    }
+
+    /// The subcommand group with the given name, e.g. `compose` for `docker`.
+    pub fn group(&self, name: &str, version: Option<Version>) -> Option<&CommandSpec> {
+        self.groups
+            .iter()
+            .find(|group| group.name == name && self.is_available(name, version))
+    }
+
+    fn is_available(&self, subcommand: &str, version: Option<Version>) -> bool {
+        let Some(version) = version else {
+            return true;
+        };
+        self.versioned
+            .iter()
+            .filter(|(name, _)| *name == subcommand)
+            .all(|(_, versions)| versions.contains(version))
+    }
+}
+
+/// A tool version. Only the parts we need to compare versions are kept.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) struct Version(pub u32, pub u32, pub u32);
+
+impl Version {
+    /// Parses the first version number in the string, so the output of
+    /// e.g. `docker --version` can be passed in as is.
+    pub fn parse(version: &str) -> Option<Self> {
+        let captures = VERSION_RE.captures(version)?;
+        let part = |i| {
+            captures
+                .get(i)
+                .map_or(Some(0), |part: regex::Match| part.as_str().parse().ok())
+        };
+        Some(Version(part(1)?, part(2)?, part(3)?))
+    }
+}
+
+/// A range of versions: `since` is inclusive and `until` is exclusive.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Versions {
+    since: Option<Version>,
+    until: Option<Version>,
+}
+
+impl Versions {
+    pub const fn since(major: u32, minor: u32, patch: u32) -> Self {
+        Versions {
+            since: Some(Version(major, minor, patch)),
+            until: None,
+        }
+    }
+
+    pub const fn until(major: u32, minor: u32, patch: u32) -> Self {
+        Versions {
+            since: None,
+            until: Some(Version(major, minor, patch)),
+        }
+    }
+
+    pub const fn and_until(self, major: u32, minor: u32, patch: u32) -> Self {
+        Versions {
+            until: Some(Version(major, minor, patch)),
+            ..self
+        }
+    }
+
+    fn contains(&self, version: Version) -> bool {
+        self.since.is_none_or(|since| since <= version)
+            && self.until.is_none_or(|until| version < until)
+    }
+}
+
+impl Tool {
+    /// The version of the tool, if the client told us (see `SessionMetadata::set_tool_version`).
+    pub fn version(&self, session_metadata: &SessionMetadata) -> Option<Version> {
+        self.names
+            .iter()
+            .find_map(|name| session_metadata.tool_version(name))
+            .and_then(Version::parse)
+    }
+
+    /// The (sub)command that was run, e.g. `docker compose` for `docker compose up -d`,
+    /// along with how it was invoked. Parts that aren't subcommands (e.g. flags) are skipped.
+    pub fn command_for(
+        &'static self,
+        input_parts: &[String],
+        version: Option<Version>,
+    ) -> (&'static CommandSpec, String) {
+        let mut command = &self.command;
+        let mut invocation = input_parts.first().cloned().unwrap_or_default();
+        for part in input_parts.iter().skip(1) {
+            if let Some(group) = command.group(part, version) {
+                command = group;
+                invocation = format!("{invocation} {part}");
+            }
+        }
+        (command, invocation)
+    }
+}
+
+/// The tool with the given command name, if we know about it.
+pub(crate) fn tool(command_name: &str) -> Option<&'static Tool> {
+    TOOLS.iter().find(|tool| tool.names.contains(&command_name))
+}
+
+pub(crate) const TOOLS: &[Tool] = &[
+    BREW,
+    CARGO,
+    DOCKER,
+    DOCKER_COMPOSE_TOOL,
+    GH,
+    GIT,
+    GREP,
+    HELM,
+    KUBECTL,
+    LS,
+    NPM,
+    PODMAN,
+    YARN,
+];
+
+// Cobra (used by e.g. kubectl, helm and gh) reports `unknown command "x" for "tool subcommand"`.
+const COBRA_UNKNOWN_COMMAND: &str = r#"(?i)unknown command "([^"]+)" for ""#;
+
+const BREW: Tool = Tool {
+    names: &["brew"],
+    rule_id: Some("BrewUnknownCommand"),
+    unknown_subcommand_errors: &["(?i)Unknown command: (.+)"],
+    command: CommandSpec {
+        name: "brew",
+        subcommands: &[
+            "analytics",
+            "autoremove",
+            "casks",
+            "cleanup",
+            "commands",
+            "completions",
+            "config",
+            "deps",
+            "desc",
+            "developer",
+            "docs",
+            "doctor",
+            "dr",
+            "fetch",
+            "formulae",
+            "gist-logs",
+            "home",
+            "homepage",
+            "info",
+            "abv",
+            "install",
+            "leaves",
+            "link",
+            "ln",
+            "list",
+            "ls",
+            "log",
+            "migrate",
+            "missing",
+            "options",
+            "outdated",
+            "pin",
+            "postinstall",
+            "readall",
+            "reinstall",
+            "search",
+            "shellenv",
+            "tap",
+            "tap-info",
+            "uninstall",
+            "remove",
+            "rm",
+            "unlink",
+            "unpin",
+            "untap",
+            "update",
+            "update-reset",
+            "upgrade",
+            "uses",
+        ],
+        flags: &[
+            "--cache",
+            "--caskroom",
+            "--cellar",
+            "--env",
+            "--prefix",
+            "--repository",
+            "--version",
+        ],
+        ..EMPTY
+    },
+};
+
+const CARGO_BUILD_FLAGS: &[&str] = &[
+    "--all-features",
+    "--all-targets",
+    "--bench",
+    "--benches",
+    "--bin",
+    "--bins",
+    "--example",
+    "--examples",
+    "--features",
+    "--frozen",
+    "--jobs",
+    "--lib",
+    "--locked",
+    "--no-default-features",
+    "--offline",
+    "--package",
+    "--profile",
+    "--quiet",
+    "--release",
+    "--target",
+    "--target-dir",
+    "--test",
+    "--tests",
+    "--verbose",
+    "--workspace",
+];
+
+const CARGO: Tool = Tool {
+    names: &["cargo"],
+    // See `CargoNoCommand`.
+    rule_id: None,
+    unknown_subcommand_errors: &[],
+    command: CommandSpec {
+        name: "cargo",
+        subcommands: &[
+            "add",
+            "clean",
+            "clippy",
+            "doc",
+            "fix",
+            "fmt",
+            "init",
+            "install",
+            "metadata",
+            "new",
+            "package",
+            "publish",
+            "remove",
+            "search",
+            "tree",
+            "uninstall",
+            "update",
+            "vendor",
+            "yank",
+        ],
+        groups: &[
+            CommandSpec {
+                name: "bench",
+                flags: CARGO_BUILD_FLAGS,
+                ..EMPTY
+            },
+            CommandSpec {
+                name: "build",
+                flags: CARGO_BUILD_FLAGS,
+                ..EMPTY
+            },
+            CommandSpec {
+                name: "check",
+                flags: CARGO_BUILD_FLAGS,
+                ..EMPTY
+            },
+            CommandSpec {
+                name: "run",
+                flags: CARGO_BUILD_FLAGS,
+                ..EMPTY
+            },
+            CommandSpec {
+                name: "test",
+                flags: CARGO_BUILD_FLAGS,
+                ..EMPTY
+            },
+        ],
+        flags: &[
+            "--color",
+            "--explain",
+            "--help",
+            "--list",
+            "--quiet",
+            "--verbose",
+            "--version",
+        ],
+        ..EMPTY
+    },
+};
+
+const DOCKER: Tool = Tool {
+    names: &["docker"],
+    rule_id: Some("DockerNoCommand"),
+    unknown_subcommand_errors: &["(?i)'(.+)' is not a docker command", DOCKER_UNKNOWN_COMMAND],
+    command: CommandSpec {
+        name: "docker",
+        subcommands: &[
+            "attach",
+            "build",
+            "commit",
+            "cp",
+            "create",
+            "diff",
+            "events",
+            "exec",
+            "export",
+            "history",
+            "images",
+            "import",
+            "info",
+            "init",
+            "inspect",
+            "kill",
+            "load",
+            "login",
+            "logout",
+            "logs",
+            "pause",
+            "port",
+            "ps",
+            "pull",
+            "push",
+            "rename",
+            "restart",
+            "rm",
+            "rmi",
+            "run",
+            "save",
+            "search",
+            "start",
+            "stats",
+            "stop",
+            "tag",
+            "top",
+            "unpause",
+            "update",
+            "version",
+            "wait",
+            "config",
+            "extension",
+            "node",
+            "plugin",
+            "sbom",
+            "scan",
+            "secret",
+            "service",
+            "stack",
+            "swarm",
+            "trust",
+        ],
+        groups: &[
+            DOCKER_COMPOSE,
+            DOCKER_CONTAINER,
+            DOCKER_IMAGE,
+            DOCKER_NETWORK,
+            DOCKER_VOLUME,
+            DOCKER_SYSTEM,
+            DOCKER_MANIFEST,
+            CommandSpec {
+                name: "buildx",
+                subcommands: &[
+                    "bake", "build", "create", "debug", "du", "history", "inspect", "ls", "prune",
+                    "rm", "stop", "use", "version",
+                ],
+                groups: &[CommandSpec {
+                    name: "imagetools",
+                    subcommands: &["create", "inspect"],
+                    ..EMPTY
+                }],
+                flags: &["--builder"],
+                ..EMPTY
+            },
+            CommandSpec {
+                name: "builder",
+                subcommands: &["build", "du", "prune"],
+                ..EMPTY
+            },
+            CommandSpec {
+                name: "context",
+                subcommands: &[
+                    "create", "export", "import", "inspect", "ls", "rm", "show", "update", "use",
+                ],
+                ..EMPTY
+            },
+        ],
+        flags: &[
+            "--config",
+            "--context",
+            "--debug",
+            "--help",
+            "--host",
+            "--log-level",
+            "--tls",
+            "--tlscacert",
+            "--tlscert",
+            "--tlskey",
+            "--tlsverify",
+            "--version",
+        ],
+        versioned: &[
+            ("buildx", Versions::since(19, 3, 0)),
+            ("compose", Versions::since(20, 10, 0)),
+            ("init", Versions::since(24, 0, 0)),
+            ("scan", Versions::until(24, 0, 0)),
+        ],
+    },
+};
+
+// Docker Compose v2 reports unknown subcommands of both `docker compose` and `docker-compose`
+// as e.g. `unknown docker command: "compose upp"`.
+const DOCKER_UNKNOWN_COMMAND: &str = r#"(?i)unknown docker command: "(.+)""#;
+
+// The standalone Compose binaries. `docker-compose` is either Compose v1 or v2, and
+// `podman-compose` reports unknown subcommands the way argparse does.
+const DOCKER_COMPOSE_TOOL: Tool = Tool {
+    names: &["docker-compose", "podman-compose"],
+    rule_id: Some("DockerComposeNoCommand"),
+    unknown_subcommand_errors: &[
+        DOCKER_UNKNOWN_COMMAND,
+        r"(?i)No such command: (\S+)",
+        r"(?i)argument command: invalid choice: '([^']+)'",
+    ],
+    command: DOCKER_COMPOSE,
+};
+
+// The subcommand groups below are shared by `docker` and `podman` (and `DOCKER_COMPOSE` by the
+// standalone Compose binaries), since Podman mirrors Docker's CLI.
+const DOCKER_COMPOSE: CommandSpec = CommandSpec {
+    name: "compose",
+    subcommands: &[
+        "build", "config", "cp", "create", "down", "events", "exec", "images", "kill", "logs",
+        "ls", "pause", "port", "ps", "pull", "push", "restart", "rm", "run", "start", "stop",
+        "top", "unpause", "up", "version", "wait", "watch",
+    ],
+    flags: &[
+        "--env-file",
+        "--file",
+        "--profile",
+        "--project-directory",
+        "--project-name",
+    ],
+    ..EMPTY
+};
+
+const DOCKER_CONTAINER: CommandSpec = CommandSpec {
+    name: "container",
+    subcommands: &[
+        "attach", "commit", "cp", "create", "diff", "exec", "export", "inspect", "kill", "logs",
+        "ls", "pause", "port", "prune", "rename", "restart", "rm", "run", "start", "stats", "stop",
+        "top", "unpause", "update", "wait",
+    ],
+    ..EMPTY
+};
+
+const DOCKER_IMAGE: CommandSpec = CommandSpec {
+    name: "image",
+    subcommands: &[
+        "build", "history", "import", "inspect", "load", "ls", "prune", "pull", "push", "rm",
+        "save", "tag",
+    ],
+    ..EMPTY
+};
+
+const DOCKER_NETWORK: CommandSpec = CommandSpec {
+    name: "network",
+    subcommands: &[
+        "connect",
+        "create",
+        "disconnect",
+        "inspect",
+        "ls",
+        "prune",
+        "rm",
+    ],
+    ..EMPTY
+};
+
+const DOCKER_VOLUME: CommandSpec = CommandSpec {
+    name: "volume",
+    subcommands: &["create", "inspect", "ls", "prune", "rm"],
+    ..EMPTY
+};
+
+const DOCKER_SYSTEM: CommandSpec = CommandSpec {
+    name: "system",
+    subcommands: &["df", "events", "info", "prune"],
+    ..EMPTY
+};
+
+const DOCKER_MANIFEST: CommandSpec = CommandSpec {
+    name: "manifest",
+    subcommands: &["annotate", "create", "inspect", "push", "rm"],
+    ..EMPTY
+};
+
+const PODMAN: Tool = Tool {
+    names: &["podman"],
+    rule_id: Some("PodmanUnknownCommand"),
+    // e.g. ``Error: unrecognized command `podman image lss` ``.
+    unknown_subcommand_errors: &["(?i)unrecognized command `podman ([^`]+)`"],
+    command: CommandSpec {
+        name: "podman",
+        subcommands: &[
+            "attach",
+            "auto-update",
+            "build",
+            "commit",
+            "cp",
+            "create",
+            "diff",
+            "events",
+            "exec",
+            "export",
+            "generate",
+            "healthcheck",
+            "history",
+            "images",
+            "import",
+            "info",
+            "init",
+            "inspect",
+            "kill",
+            "kube",
+            "load",
+            "login",
+            "logout",
+            "logs",
+            "mount",
+            "pause",
+            "port",
+            "ps",
+            "pull",
+            "push",
+            "rename",
+            "restart",
+            "rm",
+            "rmi",
+            "run",
+            "save",
+            "search",
+            "secret",
+            "start",
+            "stats",
+            "stop",
+            "tag",
+            "top",
+            "unmount",
+            "unpause",
+            "unshare",
+            "untag",
+            "update",
+            "version",
+            "wait",
+        ],
+        groups: &[
+            DOCKER_COMPOSE,
+            DOCKER_CONTAINER,
+            DOCKER_IMAGE,
+            DOCKER_NETWORK,
+            DOCKER_VOLUME,
+            DOCKER_SYSTEM,
+            DOCKER_MANIFEST,
+            CommandSpec {
+                name: "pod",
+                subcommands: &[
+                    "clone", "create", "exists", "inspect", "kill", "logs", "pause", "prune", "ps",
+                    "restart", "rm", "start", "stats", "stop", "top", "unpause",
+                ],
+                ..EMPTY
+            },
+            CommandSpec {
+                name: "machine",
+                subcommands: &[
+                    "info", "init", "inspect", "list", "os", "reset", "rm", "set", "ssh", "start",
+                    "stop",
+                ],
+                ..EMPTY
+            },
+        ],
+        flags: &[
+            "--connection",
+            "--help",
+            "--log-level",
+            "--remote",
+            "--root",
+            "--runroot",
+            "--url",
+            "--version",
+        ],
+        versioned: &[("compose", Versions::since(4, 7, 0))],
+    },
+};
+
+const GH: Tool = Tool {
+    names: &["gh"],
+    rule_id: Some("GhUnknownCommand"),
+    unknown_subcommand_errors: &[COBRA_UNKNOWN_COMMAND],
+    command: CommandSpec {
+        name: "gh",
+        subcommands: &[
+            "alias",
+            "api",
+            "browse",
+            "cache",
+            "codespace",
+            "completion",
+            "config",
+            "extension",
+            "gist",
+            "gpg-key",
+            "label",
+            "org",
+            "project",
+            "ruleset",
+            "search",
+            "secret",
+            "ssh-key",
+            "status",
+            "variable",
+        ],
+        groups: &[
+            CommandSpec {
+                name: "auth",
+                subcommands: &[
+                    "login",
+                    "logout",
+                    "refresh",
+                    "setup-git",
+                    "status",
+                    "switch",
+                    "token",
+                ],
+                ..EMPTY
+            },
+            CommandSpec {
+                name: "issue",
+                subcommands: &[
+                    "close", "comment", "create", "delete", "develop", "edit", "list", "lock",
+                    "pin", "reopen", "status", "transfer", "unlock", "unpin", "view",
+                ],
+                ..EMPTY
+            },
+            CommandSpec {
+                name: "pr",
+                subcommands: &[
+                    "checkout", "checks", "close", "comment", "create", "diff", "edit", "list",
+                    "lock", "merge", "ready", "reopen", "review", "status", "unlock", "view",
+                ],
+                ..EMPTY
+            },
+            CommandSpec {
+                name: "release",
+                subcommands: &[
+                    "create",
+                    "delete",
+                    "delete-asset",
+                    "download",
+                    "edit",
+                    "list",
+                    "upload",
+                    "view",
+                ],
+                ..EMPTY
+            },
+            CommandSpec {
+                name: "repo",
+                subcommands: &[
+                    "archive",
+                    "clone",
+                    "create",
+                    "delete",
+                    "deploy-key",
+                    "edit",
+                    "fork",
+                    "list",
+                    "rename",
+                    "set-default",
+                    "sync",
+                    "unarchive",
+                    "view",
+                ],
+                ..EMPTY
+            },
+            CommandSpec {
+                name: "run",
+                subcommands: &[
+                    "cancel", "delete", "download", "list", "rerun", "view", "watch",
+                ],
+                ..EMPTY
+            },
+            CommandSpec {
+                name: "workflow",
+                subcommands: &["disable", "enable", "list", "run", "view"],
+                ..EMPTY
+            },
+        ],
+        flags: &["--help", "--repo", "--version"],
+        ..EMPTY
+    },
+};
+
+const GIT: Tool = Tool {
+    names: &["git"],
+    rule_id: None,
+    unknown_subcommand_errors: &[],
+    command: CommandSpec {
+        name: "git",
+        // Only porcelain commands.
+        subcommands: &[
+            "add",
+            "am",
+            "archive",
+            "bisect",
+            "blame",
+            "branch",
+            "bundle",
+            "checkout",
+            "cherry-pick",
+            "clean",
+            "clone",
+            "config",
+            "describe",
+            "diff",
+            "fetch",
+            "format-patch",
+            "gc",
+            "grep",
+            "init",
+            "log",
+            "merge",
+            "mv",
+            "notes",
+            "pull",
+            "rebase",
+            "reflog",
+            "remote",
+            "reset",
+            "restore",
+            "revert",
+            "rm",
+            "shortlog",
+            "show",
+            "stash",
+            "status",
+            "submodule",
+            "switch",
+            "tag",
+            "worktree",
+        ],
+        groups: &[
+            CommandSpec {
+                name: "commit",
+                flags: &[
+                    "--all",
+                    "--allow-empty",
+                    "--amend",
+                    "--author",
+                    "--date",
+                    "--dry-run",
+                    "--edit",
+                    "--fixup",
+                    "--interactive",
+                    "--message",
+                    "--no-edit",
+                    "--no-verify",
+                    "--patch",
+                    "--quiet",
+                    "--reuse-message",
+                    "--signoff",
+                    "--squash",
+                    "--verbose",
+                ],
+                ..EMPTY
+            },
+            CommandSpec {
+                name: "push",
+                flags: &[
+                    "--all",
+                    "--delete",
+                    "--dry-run",
+                    "--force",
+                    "--force-with-lease",
+                    "--no-verify",
+                    "--quiet",
+                    "--set-upstream",
+                    "--tags",
+                    "--verbose",
+                ],
+                ..EMPTY
+            },
+        ],
+        flags: &[
+            "--bare",
+            "--git-dir",
+            "--help",
+            "--no-pager",
+            "--paginate",
+            "--version",
+            "--work-tree",
+        ],
+        versioned: &[
+            ("restore", Versions::since(2, 23, 0)),
+            ("switch", Versions::since(2, 23, 0)),
+        ],
+    },
+};
+
+const GREP: Tool = Tool {
+    names: &["grep", "egrep", "fgrep"],
+    rule_id: None,
+    unknown_subcommand_errors: &[],
+    command: CommandSpec {
+        name: "grep",
+        flags: &[
+            "--after-context",
+            "--basic-regexp",
+            "--before-context",
+            "--binary-files",
+            "--byte-offset",
+            "--color",
+            "--colour",
+            "--context",
+            "--count",
+            "--dereference-recursive",
+            "--devices",
+            "--directories",
+            "--exclude",
+            "--exclude-dir",
+            "--exclude-from",
+            "--extended-regexp",
+            "--file",
+            "--files-with-matches",
+            "--files-without-match",
+            "--fixed-strings",
+            "--help",
+            "--ignore-case",
+            "--include",
+            "--initial-tab",
+            "--invert-match",
+            "--label",
+            "--line-buffered",
+            "--line-number",
+            "--line-regexp",
+            "--max-count",
+            "--no-filename",
+            "--no-ignore-case",
+            "--no-messages",
+            "--null",
+            "--null-data",
+            "--only-matching",
+            "--perl-regexp",
+            "--quiet",
+            "--recursive",
+            "--regexp",
+            "--silent",
+            "--text",
+            "--version",
+            "--with-filename",
+            "--word-regexp",
+        ],
+        ..EMPTY
+    },
+};
+
+const HELM: Tool = Tool {
+    names: &["helm"],
+    rule_id: Some("HelmUnknownCommand"),
+    unknown_subcommand_errors: &[COBRA_UNKNOWN_COMMAND],
+    command: CommandSpec {
+        name: "helm",
+        subcommands: &[
+            "completion",
+            "create",
+            "env",
+            "history",
+            "install",
+            "lint",
+            "list",
+            "package",
+            "pull",
+            "push",
+            "rollback",
+            "status",
+            "template",
+            "test",
+            "uninstall",
+            "upgrade",
+            "verify",
+            "version",
+        ],
+        groups: &[
+            CommandSpec {
+                name: "dependency",
+                subcommands: &["build", "list", "update"],
+                ..EMPTY
+            },
+            CommandSpec {
+                name: "get",
+                subcommands: &["all", "hooks", "manifest", "metadata", "notes", "values"],
+                ..EMPTY
+            },
+            CommandSpec {
+                name: "plugin",
+                subcommands: &["install", "list", "uninstall", "update"],
+                ..EMPTY
+            },
+            CommandSpec {
+                name: "registry",
+                subcommands: &["login", "logout"],
+                ..EMPTY
+            },
+            CommandSpec {
+                name: "repo",
+                subcommands: &["add", "index", "list", "remove", "update"],
+                ..EMPTY
+            },
+            CommandSpec {
+                name: "search",
+                subcommands: &["hub", "repo"],
+                ..EMPTY
+            },
+            CommandSpec {
+                name: "show",
+                subcommands: &["all", "chart", "crds", "readme", "values"],
+                ..EMPTY
+            },
+        ],
+        flags: &[
+            "--debug",
+            "--help",
+            "--kube-context",
+            "--kubeconfig",
+            "--namespace",
+            "--registry-config",
+        ],
+        versioned: &[
+            ("push", Versions::since(3, 7, 0)),
+            ("registry", Versions::since(3, 7, 0)),
+        ],
+    },
+};
+
+const KUBECTL: Tool = Tool {
+    names: &["kubectl"],
+    rule_id: Some("KubectlUnknownCommand"),
+    unknown_subcommand_errors: &[COBRA_UNKNOWN_COMMAND],
+    command: CommandSpec {
+        name: "kubectl",
+        subcommands: &[
+            "annotate",
+            "api-resources",
+            "api-versions",
+            "apply",
+            "attach",
+            "autoscale",
+            "certificate",
+            "cluster-info",
+            "completion",
+            "cordon",
+            "cp",
+            "create",
+            "debug",
+            "delete",
+            "describe",
+            "diff",
+            "drain",
+            "edit",
+            "events",
+            "exec",
+            "explain",
+            "expose",
+            "get",
+            "kustomize",
+            "label",
+            "logs",
+            "patch",
+            "plugin",
+            "port-forward",
+            "proxy",
+            "replace",
+            "run",
+            "scale",
+            "set",
+            "taint",
+            "top",
+            "uncordon",
+            "version",
+            "wait",
+        ],
+        groups: &[
+            CommandSpec {
+                name: "auth",
+                subcommands: &["can-i", "reconcile", "whoami"],
+                ..EMPTY
+            },
+            CommandSpec {
+                name: "config",
+                subcommands: &[
+                    "current-context",
+                    "delete-cluster",
+                    "delete-context",
+                    "delete-user",
+                    "get-clusters",
+                    "get-contexts",
+                    "get-users",
+                    "rename-context",
+                    "set",
+                    "set-cluster",
+                    "set-context",
+                    "set-credentials",
+                    "unset",
+                    "use-context",
+                    "view",
+                ],
+                ..EMPTY
+            },
+            CommandSpec {
+                name: "rollout",
+                subcommands: &["history", "pause", "restart", "resume", "status", "undo"],
+                ..EMPTY
+            },
+        ],
+        flags: &[
+            "--context",
+            "--help",
+            "--kubeconfig",
+            "--namespace",
+            "--output",
+            "--selector",
+        ],
+        versioned: &[("events", Versions::since(1, 26, 0))],
+    },
+};
+
+const LS: Tool = Tool {
+    names: &["ls"],
+    rule_id: None,
+    unknown_subcommand_errors: &[],
+    command: CommandSpec {
+        name: "ls",
+        flags: &[
+            "--all",
+            "--almost-all",
+            "--author",
+            "--block-size",
+            "--classify",
+            "--color",
+            "--context",
+            "--dereference",
+            "--dereference-command-line",
+            "--directory",
+            "--escape",
+            "--file-type",
+            "--format",
+            "--full-time",
+            "--group-directories-first",
+            "--help",
+            "--hide",
+            "--hide-control-chars",
+            "--human-readable",
+            "--hyperlink",
+            "--ignore",
+            "--ignore-backups",
+            "--indicator-style",
+            "--inode",
+            "--kibibytes",
+            "--literal",
+            "--no-group",
+            "--numeric-uid-gid",
+            "--quote-name",
+            "--quoting-style",
+            "--recursive",
+            "--reverse",
+            "--show-control-chars",
+            "--si",
+            "--size",
+            "--sort",
+            "--tabsize",
+            "--time",
+            "--time-style",
+            "--version",
+            "--width",
+        ],
+        ..EMPTY
+    },
+};
+
+const NPM: Tool = Tool {
+    names: &["npm"],
+    rule_id: Some("NpmUnknownCommand"),
+    unknown_subcommand_errors: &[r#"(?i)Unknown command: "(.+)""#],
+    command: CommandSpec {
+        name: "npm",
+        subcommands: &[
+            "access",
+            "adduser",
+            "audit",
+            "bin",
+            "bugs",
+            "cache",
+            "ci",
+            "completion",
+            "config",
+            "dedupe",
+            "deprecate",
+            "diff",
+            "dist-tag",
+            "docs",
+            "doctor",
+            "edit",
+            "exec",
+            "explain",
+            "explore",
+            "find-dupes",
+            "fund",
+            "get",
+            "help",
+            "hook",
+            "init",
+            "install",
+            "install-ci-test",
+            "install-test",
+            "link",
+            "ll",
+            "login",
+            "logout",
+            "ls",
+            "org",
+            "outdated",
+            "owner",
+            "pack",
+            "ping",
+            "pkg",
+            "prefix",
+            "profile",
+            "prune",
+            "publish",
+            "query",
+            "rebuild",
+            "repo",
+            "restart",
+            "root",
+            "run-script",
+            "sbom",
+            "search",
+            "set",
+            "set-script",
+            "shrinkwrap",
+            "star",
+            "stars",
+            "start",
+            "stop",
+            "team",
+            "test",
+            "token",
+            "uninstall",
+            "unpublish",
+            "unstar",
+            "update",
+            "version",
+            "view",
+            "whoami",
+        ],
+        flags: &[
+            "--global",
+            "--help",
+            "--loglevel",
+            "--prefix",
+            "--save-dev",
+            "--version",
+            "--workspace",
+        ],
+        versioned: &[
+            ("bin", Versions::until(9, 0, 0)),
+            ("exec", Versions::since(7, 0, 0)),
+            ("pkg", Versions::since(7, 20, 0)),
+            ("query", Versions::since(8, 16, 0)),
+            ("sbom", Versions::since(10, 0, 0)),
+            ("set-script", Versions::since(7, 0, 0).and_until(9, 0, 0)),
+        ],
+        ..EMPTY
+    },
+};
+
+const YARN: Tool = Tool {
+    names: &["yarn"],
+    rule_id: Some("YarnCommandNotFound"),
+    unknown_subcommand_errors: &["(?i)Command \"(.+)\" not found"],
+    command: CommandSpec {
+        name: "yarn",
+        subcommands: &[
+            "access",
+            "add",
+            "audit",
+            "autoclean",
+            "bin",
+            "cache",
+            "check",
+            "config",
+            "create",
+            "exec",
+            "generate-lock-entry",
+            "generateLockEntry",
+            "global",
+            "help",
+            "import",
+            "info",
+            "init",
+            "install",
+            "licenses",
+            "link",
+            "list",
+            "login",
+            "logout",
+            "node",
+            "outdated",
+            "owner",
+            "pack",
+            "policies",
+            "publish",
+            "remove",
+            "run",
+            "tag",
+            "team",
+            "unlink",
+            "unplug",
+            "upgrade",
+            "upgrade-interactive",
+            "upgradeInteractive",
+            "version",
+            "versions",
+            "why",
+            "workspace",
+        ],
+        flags: &["--cwd", "--help", "--silent", "--verbose", "--version"],
+        ..EMPTY
+    },
+};
+
+#[cfg(test)]
+mod tests {
+    use super::{tool, Version, Versions};
+
+    #[test]
+    fn test_version_parse() {
+        assert_eq!(Version::parse("10.2.4"), Some(Version(10, 2, 4)));
+        assert_eq!(Version::parse("v1.26"), Some(Version(1, 26, 0)));
+        assert_eq!(
+            Version::parse("Docker version 24.0.7, build afdd53b"),
+            Some(Version(24, 0, 7))
+        );
+        assert_eq!(Version::parse("latest"), None);
+    }
+
+    #[test]
+    fn test_versioned_subcommands() {
+        let npm = &tool("npm").unwrap().command;
+        let has =
+            |version, subcommand| npm.subcommand_names(version).any(|name| name == subcommand);
+
+        assert!(has(None, "bin"));
+        assert!(has(Some(Version(8, 19, 0)), "bin"));
+        assert!(!has(Some(Version(9, 0, 0)), "bin"));
+        assert!(!has(Some(Version(6, 14, 0)), "set-script"));
+        assert!(has(Some(Version(8, 0, 0)), "set-script"));
+        assert!(!has(Some(Version(9, 1, 0)), "set-script"));
+
+        let range = Versions::since(1, 0, 0).and_until(2, 0, 0);
+        assert!(range.contains(Version(1, 5, 0)));
+        assert!(!range.contains(Version(2, 0, 0)));
+    }
+
+    #[test]
+    fn test_groups() {
+        let docker = &tool("docker").unwrap().command;
+        assert!(docker.group("compose", None).is_some());
+        assert!(docker.group("compose", Some(Version(19, 3, 0))).is_none());
+        assert!(docker.group("ps", None).is_none());
+        assert!(docker.subcommand_names(None).any(|name| name == "compose"));
+    }
+}
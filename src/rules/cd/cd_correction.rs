@@ -24,15 +24,19 @@ impl Rule for CdCorrection {
     fn generate_command_corrections<'a>(
         &self,
         command: &'a Command,
-        _session_metadata: &'a SessionMetadata,
+        session_metadata: &'a SessionMetadata,
     ) -> Option<Vec<RuleCorrection<'a>>> {
         let wrong_dirname = RE
             .captures(command.input)
             .and_then(|captures| captures.get(1))
             .map(|regex_match| Path::new(regex_match.as_str()))?;
 
-        let corrected_path =
-            correct_path_at_every_level(wrong_dirname, command.working_dir?, |path| path.is_dir())?;
+        let corrected_path = correct_path_at_every_level(
+            wrong_dirname,
+            command.working_dir?,
+            |path| path.is_dir(),
+            session_metadata.matcher(self.id()),
+        )?;
 
         Some(vec![vec!["cd".to_owned(), corrected_path].into()])
     }
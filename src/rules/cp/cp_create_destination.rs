@@ -36,7 +36,16 @@ impl Rule for CpCreateDestination {
 
 #[cfg(test)]
 mod tests {
-    use crate::test_utils::basic_corrections;
+    use crate::test_utils::{basic_corrections, basic_previews};
+
+    #[test]
+    fn test_cp_create_destination_no_preview() {
+        // cp can't do a dry run, and previewing `mkdir` would create the directory.
+        assert_eq!(
+            basic_previews("cp foo bar/", "cp: directory bar does not exist"),
+            vec![None]
+        );
+    }
 
     #[test]
     fn test_cp_create_destination() {
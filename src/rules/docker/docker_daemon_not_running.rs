@@ -0,0 +1,124 @@
+use crate::rules::Rule;
+use crate::{default_rule_id, Command, RuleCorrection, SessionMetadata};
+use lazy_static::lazy_static;
+use regex::Regex;
+
+lazy_static! {
+    // e.g. `Cannot connect to the Docker daemon at unix:///var/run/docker.sock. Is the docker daemon running?`
+    // (older versions don't say where the daemon was expected).
+    static ref RE: Regex = Regex::new(
+        r"(?i)cannot connect to the docker daemon(?: at (\S+?))?\.? is the docker daemon running"
+    )
+    .unwrap();
+}
+
+// Where the daemon listens when it's installed as a system service.
+const SYSTEM_SOCKETS: &[&str] = &["unix:///var/run/docker.sock", "unix:///run/docker.sock"];
+
+/*
+Starts the docker daemon when a command can't connect to it: the system service
+(`sudo systemctl start docker`), Docker Desktop or Colima, depending on the socket docker tried.
+If docker tried another host (e.g. `tcp://` or `ssh://` from a context or `DOCKER_HOST`),
+switches back to the default context or runs the command without `DOCKER_HOST` instead.
+*/
+pub(crate) struct DockerDaemonNotRunning;
+impl Rule for DockerDaemonNotRunning {
+    default_rule_id!(DockerDaemonNotRunning);
+
+    fn matches(&self, command: &Command, _session_metadata: &SessionMetadata) -> bool {
+        RE.is_match(command.output)
+    }
+
+    fn generate_command_corrections<'a>(
+        &self,
+        command: &'a Command,
+        session_metadata: &'a SessionMetadata,
+    ) -> Option<Vec<RuleCorrection<'a>>> {
+        let host = RE
+            .captures(command.output)?
+            .get(1)
+            .map(|host| host.as_str());
+
+        let fixes: &[&str] = match host {
+            None => &["sudo systemctl start docker", "open -a Docker"],
+            Some(host) if SYSTEM_SOCKETS.contains(&host) => {
+                // On macOS, the system socket is a link to Docker Desktop's.
+                if session_metadata.is_top_level_command("systemctl") {
+                    &["sudo systemctl start docker"]
+                } else if session_metadata.is_top_level_command("open") {
+                    &["open -a Docker"]
+                } else {
+                    &["sudo systemctl start docker", "open -a Docker"]
+                }
+            }
+            Some(host) if host.contains("/.docker/") => &["open -a Docker"],
+            Some(host) if host.contains("/.colima/") => &["colima start"],
+            Some(_) => &["docker context use default"],
+        };
+        let mut corrections = fixes
+            .iter()
+            .map(|fix| RuleCorrection::and(*fix, command.input))
+            .collect::<Vec<_>>();
+        if host.is_some_and(|host| !host.starts_with("unix://")) {
+            let without_host = ["env", "-u", "DOCKER_HOST"]
+                .into_iter()
+                .map(str::to_owned)
+                .chain(command.input_parts().iter().cloned())
+                .collect::<Vec<_>>();
+            corrections.push(without_host.into());
+        }
+        Some(corrections)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::{basic_corrections, regular_corrections};
+    use crate::{Command, ExitCode, SessionMetadata};
+
+    #[test]
+    fn test_system_daemon() {
+        let command = || {
+            Command::new(
+                "docker ps",
+                "Cannot connect to the Docker daemon at unix:///var/run/docker.sock. Is the docker daemon running?",
+                ExitCode(1),
+            )
+        };
+        assert_eq!(
+            regular_corrections(command(), &SessionMetadata::new()),
+            vec![
+                "sudo systemctl start docker && docker ps",
+                "open -a Docker && docker ps"
+            ]
+        );
+
+        let mut session_metadata = SessionMetadata::new();
+        session_metadata.set_executables(["docker", "systemctl"]);
+        assert_eq!(
+            regular_corrections(command(), &session_metadata),
+            vec!["sudo systemctl start docker && docker ps"]
+        );
+    }
+
+    #[test]
+    fn test_other_hosts() {
+        assert_eq!(
+            basic_corrections(
+                "docker compose up -d",
+                "Cannot connect to the Docker daemon at unix:///Users/me/.colima/default/docker.sock. Is the docker daemon running?"
+            ),
+            vec!["colima start && docker compose up -d"]
+        );
+        assert_eq!(
+            basic_corrections(
+                "docker images",
+                "Cannot connect to the Docker daemon at tcp://10.0.0.5:2375. Is the docker daemon running?"
+            ),
+            vec![
+                "docker context use default && docker images",
+                "env -u DOCKER_HOST docker images"
+            ]
+        );
+    }
+}
@@ -1,3 +1,4 @@
+use crate::risk::Risk;
 use crate::rules::Rule;
 use crate::{default_rule_id, Command, RuleCorrection, SessionMetadata};
 
@@ -21,13 +22,41 @@ impl Rule for DockerImageRm {
         let mut replacement = command.input_parts().to_vec();
         let rm_pos = replacement.iter().position(|p| p == "rm")?;
         replacement.insert(rm_pos + 1, "--force".to_owned());
-        Some(vec![replacement.into()])
+
+        // Docker can't do a dry run, so the preview lists the containers using the images.
+        let images = replacement[rm_pos + 1..]
+            .iter()
+            .filter(|part| !part.starts_with('-'));
+        let mut preview = vec!["docker".to_owned(), "ps".to_owned(), "--all".to_owned()];
+        for image in images {
+            preview.extend(["--filter".to_owned(), format!("ancestor={image}")]);
+        }
+        Some(vec![RuleCorrection::from(replacement).with_preview(preview)])
+    }
+
+    /// Forcing the removal also untags images used by containers.
+    fn risk(&self) -> Risk {
+        Risk::Destructive
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::test_utils::basic_corrections;
+    use crate::test_utils::{basic_corrections, basic_previews};
+
+    #[test]
+    fn test_docker_image_rm_preview() {
+        assert_eq!(
+            basic_previews(
+                "docker image rm ssh_image other_image",
+                r#"Error response from daemon: conflict: unable to remove repository reference "ssh_image" (must force)"#
+            ),
+            vec![Some(
+                "docker ps --all --filter 'ancestor=ssh_image' --filter 'ancestor=other_image'"
+                    .to_owned()
+            )]
+        );
+    }
 
     #[test]
     fn test_docker_image_rm() {
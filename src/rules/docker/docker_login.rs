@@ -1,7 +1,12 @@
 use crate::rules::Rule;
-use crate::{default_rule_id, Command, RuleCorrection, SessionMetadata};
+use crate::{
+    default_rule_id, Command, PlaceholderKind, RuleCorrection, SessionMetadata, TemplatePart,
+};
+use itertools::Itertools;
 
-/// Fixes a docker command where you have to login first.
+/// Fixes a docker command where you have to login first. If the image is on another registry
+/// than Docker Hub (e.g. `ghcr.io/owner/app`), logs in to that registry. If the command has
+/// images on several registries, the registry is a placeholder.
 pub(crate) struct DockerLogin;
 impl Rule for DockerLogin {
     default_rule_id!(DockerLogin);
@@ -17,13 +22,93 @@ impl Rule for DockerLogin {
         command: &'a Command,
         _session_metadata: &'a SessionMetadata,
     ) -> Option<Vec<RuleCorrection<'a>>> {
-        Some(vec![RuleCorrection::and("docker login", command.input)])
+        let registries = images(command.input_parts())
+            .filter_map(registry_of)
+            .unique()
+            .collect_vec();
+        let login = match registries.as_slice() {
+            [] => RuleCorrection::from("docker login"),
+            [registry] => RuleCorrection::from(vec!["docker", "login", registry]),
+            _ => RuleCorrection::Template(vec![
+                "docker".into(),
+                "login".into(),
+                TemplatePart::placeholder("registry", PlaceholderKind::Registry),
+            ]),
+        };
+        Some(vec![RuleCorrection::and(login, command.input)])
     }
 }
 
+// Options whose value is a separate argument that isn't an image, e.g. `-v ./data:/data`.
+const OPTIONS_WITH_VALUE: &[&str] = &[
+    "-e",
+    "--env",
+    "--env-file",
+    "-f",
+    "--file",
+    "-h",
+    "--hostname",
+    "-l",
+    "--label",
+    "--mount",
+    "--name",
+    "--network",
+    "-p",
+    "--publish",
+    "--platform",
+    "-t",
+    "--tag",
+    "-u",
+    "--user",
+    "-v",
+    "--volume",
+    "-w",
+    "--workdir",
+];
+
+/// The arguments that may be images: the ones that aren't options or their values. The
+/// subcommand is one of them, but isn't a registry.
+fn images(input_parts: &[String]) -> impl Iterator<Item = &str> {
+    let mut skip_value = false;
+    input_parts.iter().skip(1).filter_map(move |part| {
+        let is_value = std::mem::take(&mut skip_value);
+        if part.starts_with('-') {
+            skip_value = OPTIONS_WITH_VALUE.contains(&part.as_str());
+            return None;
+        }
+        (!is_value).then_some(part.as_str())
+    })
+}
+
+/// The registry of an image reference, e.g. `ghcr.io` for `ghcr.io/owner/app:latest`.
+/// Like docker, only treats the first component as a registry if it looks like a host: a
+/// dotted hostname, a `host:port` or `localhost`.
+fn registry_of(image: &str) -> Option<&str> {
+    let (first, _) = image.split_once('/')?;
+    let (host, port) = match first.split_once(':') {
+        Some((host, port)) => (host, Some(port)),
+        None => (first, None),
+    };
+    let is_hostname = !host.is_empty()
+        && !host.starts_with(['.', '~', '-'])
+        && !host.ends_with('.')
+        && host
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '-');
+    let is_port = |port: &str| !port.is_empty() && port.chars().all(|c| c.is_ascii_digit());
+    let is_host = match port {
+        Some(port) => is_hostname && is_port(port),
+        None => is_hostname && (host.contains('.') || host == "localhost"),
+    };
+    is_host.then_some(first)
+}
+
 #[cfg(test)]
 mod tests {
     use crate::test_utils::basic_corrections;
+    use crate::{
+        correct_command, Command, ExitCode, Placeholder, PlaceholderKind, SessionMetadata,
+    };
 
     #[test]
     fn test_docker_login() {
@@ -36,4 +121,61 @@ mod tests {
             vec!["docker login && docker push repo/image"]
         );
     }
+
+    #[test]
+    fn test_docker_login_registry() {
+        assert_eq!(
+            basic_corrections(
+                "docker push ghcr.io/owner/app:latest",
+                "denied: requested access to the resource is denied\nunauthorized: access denied"
+            ),
+            vec!["docker login ghcr.io && docker push ghcr.io/owner/app:latest"]
+        );
+    }
+
+    #[test]
+    fn test_docker_login_not_a_registry() {
+        // Volumes and other option values aren't images.
+        assert_eq!(
+            basic_corrections(
+                "docker run -v ./data:/data -v ~/.cache:/cache repo/image",
+                "docker: Error response from daemon: pull access denied for repo/image, repository does not exist or may require 'docker login'."
+            ),
+            vec!["docker login && docker run -v ./data:/data -v ~/.cache:/cache repo/image"]
+        );
+        assert_eq!(
+            basic_corrections(
+                "docker run --mount type=bind,source=a.b/c,target=/c localhost:5000/app",
+                "Error response from daemon: pull access denied for localhost:5000/app"
+            ),
+            vec!["docker login localhost:5000 && docker run --mount type=bind,source=a.b/c,target=/c localhost:5000/app"]
+        );
+    }
+
+    #[test]
+    fn test_docker_login_several_registries() {
+        let command = Command::new(
+            "docker tag ghcr.io/owner/app:latest registry.example.com/app:latest",
+            "Error response from daemon: pull access denied for ghcr.io/owner/app, repository does not exist or may require 'docker login'",
+            ExitCode(1),
+        );
+        let corrections = correct_command(command, &SessionMetadata::new());
+        let [correction] = corrections.as_slice() else {
+            panic!("expected a single correction");
+        };
+        assert_eq!(
+            correction.command,
+            "docker login <registry> && docker tag ghcr.io/owner/app:latest registry.example.com/app:latest"
+        );
+        assert!(correction.needs_input());
+        assert_eq!(
+            correction.placeholders,
+            vec![Placeholder {
+                name: "registry".to_owned(),
+                kind: PlaceholderKind::Registry,
+                default: None,
+                span: 13..23
+            }]
+        );
+    }
 }
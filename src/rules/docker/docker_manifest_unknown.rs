@@ -0,0 +1,111 @@
+use crate::rules::docker::image::{find_in_command, split_tag};
+use crate::rules::util::{get_single_closest_match, new_commands_from_suggestions};
+use crate::rules::Rule;
+use crate::{default_rule_id, Command, RuleCorrection, SessionMetadata};
+use itertools::Itertools;
+use lazy_static::lazy_static;
+use regex::Regex;
+
+lazy_static! {
+    // e.g. `Error response from daemon: manifest for nginx:1.255 not found: manifest unknown: manifest unknown`.
+    static ref RE: Regex = Regex::new(r"(?i)manifest for (\S+) not found").unwrap();
+}
+
+/*
+Fixes pulling a tag that doesn't exist: suggests the closest tag of the image the client told us
+about (see `SessionMetadata::set_docker_images`), then `:latest`. If `latest` is the tag that
+doesn't exist, suggests the image's known tags instead.
+*/
+pub(crate) struct DockerManifestUnknown;
+impl Rule for DockerManifestUnknown {
+    default_rule_id!(DockerManifestUnknown);
+
+    fn matches(&self, command: &Command, _session_metadata: &SessionMetadata) -> bool {
+        RE.is_match(command.output)
+    }
+
+    fn generate_command_corrections<'a>(
+        &self,
+        command: &'a Command,
+        session_metadata: &'a SessionMetadata,
+    ) -> Option<Vec<RuleCorrection<'a>>> {
+        let image = RE.captures(command.output)?.get(1)?.as_str();
+        let in_command = find_in_command(command.input_parts(), image)?;
+        let (repository, tag) = split_tag(image);
+        let tag = tag.unwrap_or("latest");
+
+        let known_tags = session_metadata
+            .docker_images
+            .iter()
+            .filter_map(|image| match split_tag(image) {
+                (image_repository, Some(tag)) if image_repository == repository => Some(tag),
+                _ => None,
+            })
+            .filter(|known_tag| *known_tag != tag)
+            .sorted()
+            .collect_vec();
+
+        let tags = if tag == "latest" {
+            known_tags
+        } else {
+            let matcher = session_metadata.matcher(self.id());
+            get_single_closest_match(tag, known_tags, matcher)
+                .into_iter()
+                .chain(["latest"])
+                .collect_vec()
+        };
+        let suggestions = tags
+            .into_iter()
+            .unique()
+            .map(|tag| format!("{repository}:{tag}"));
+        new_commands_from_suggestions(suggestions, command.input_parts(), in_command)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::{basic_corrections, regular_corrections};
+    use crate::{Command, ExitCode, SessionMetadata};
+
+    #[test]
+    fn test_unknown_tag() {
+        let mut session_metadata = SessionMetadata::new();
+        session_metadata.set_docker_images(["nginx:1.25", "nginx:1.24-alpine", "redis:7"]);
+        let command = Command::new(
+            "docker pull nginx:1.255",
+            "Error response from daemon: manifest for nginx:1.255 not found: manifest unknown: manifest unknown",
+            ExitCode(1),
+        );
+        assert_eq!(
+            regular_corrections(command, &session_metadata),
+            vec!["docker pull nginx:1.25", "docker pull nginx:latest"]
+        );
+
+        assert_eq!(
+            basic_corrections(
+                "docker pull nginx:1.255",
+                "Error response from daemon: manifest for nginx:1.255 not found: manifest unknown: manifest unknown"
+            ),
+            vec!["docker pull nginx:latest"]
+        );
+    }
+
+    #[test]
+    fn test_no_latest_tag() {
+        let mut session_metadata = SessionMetadata::new();
+        session_metadata.set_docker_images(["ghcr.io/org/app:v2", "ghcr.io/org/app:v1"]);
+        let command = Command::new(
+            "docker run --rm ghcr.io/org/app",
+            "Unable to find image 'ghcr.io/org/app:latest' locally
+docker: Error response from daemon: manifest for ghcr.io/org/app:latest not found: manifest unknown: manifest unknown.",
+            ExitCode(125),
+        );
+        assert_eq!(
+            regular_corrections(command, &session_metadata),
+            vec![
+                "docker run --rm ghcr.io/org/app:v1",
+                "docker run --rm ghcr.io/org/app:v2"
+            ]
+        );
+    }
+}
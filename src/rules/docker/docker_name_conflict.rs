@@ -0,0 +1,93 @@
+use crate::rules::Rule;
+use crate::{default_rule_id, Command, RuleCorrection, SessionMetadata};
+use lazy_static::lazy_static;
+use regex::Regex;
+
+lazy_static! {
+    // e.g. `Conflict. The container name "/web" is already in use by container "3f1c...".`
+    static ref RE: Regex =
+        Regex::new(r#"(?i)the container name "/?([^"]+)" is already in use"#).unwrap();
+}
+
+/*
+Fixes creating a container with the name of an existing one: either removes the existing
+container (`docker rm -f web && docker run --name web nginx`) or picks a name that isn't
+taken (`docker run --name web-2 nginx`).
+*/
+pub(crate) struct DockerNameConflict;
+impl Rule for DockerNameConflict {
+    default_rule_id!(DockerNameConflict);
+
+    fn matches(&self, command: &Command, _session_metadata: &SessionMetadata) -> bool {
+        RE.is_match(command.output)
+    }
+
+    fn generate_command_corrections<'a>(
+        &self,
+        command: &'a Command,
+        session_metadata: &'a SessionMetadata,
+    ) -> Option<Vec<RuleCorrection<'a>>> {
+        let name = RE.captures(command.output)?.get(1)?.as_str();
+        let remove = vec![
+            "docker".to_owned(),
+            "rm".to_owned(),
+            "-f".to_owned(),
+            name.to_owned(),
+        ];
+        let mut corrections = vec![RuleCorrection::and(remove, command.input)];
+
+        let new_name = (2..).map(|i| format!("{name}-{i}")).find(|new_name| {
+            !session_metadata
+                .docker_containers
+                .contains(new_name.as_str())
+        })?;
+        let parts = command.input_parts();
+        let renamed = parts.iter().enumerate().find_map(|(i, part)| {
+            let mut replacement = parts.to_vec();
+            if part == name && i > 0 && parts[i - 1] == "--name" {
+                replacement[i] = new_name.clone();
+            } else if part.strip_prefix("--name=") == Some(name) {
+                replacement[i] = format!("--name={new_name}");
+            } else {
+                return None;
+            }
+            Some(replacement)
+        });
+        corrections.extend(renamed.map(RuleCorrection::from));
+        Some(corrections)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::{basic_corrections, regular_corrections};
+    use crate::{Command, ExitCode, SessionMetadata};
+
+    const OUTPUT: &str = r#"docker: Error response from daemon: Conflict. The container name "/web" is already in use by container "3f1c0e4b2a". You have to remove (or rename) that container to be able to reuse that name.
+See 'docker run --help'."#;
+
+    #[test]
+    fn test_name_conflict() {
+        assert_eq!(
+            basic_corrections("docker run -d --name web nginx", OUTPUT),
+            vec![
+                "docker rm -f web && docker run -d --name web nginx",
+                "docker run -d --name web-2 nginx"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_taken_names() {
+        let mut session_metadata = SessionMetadata::new();
+        session_metadata.set_docker_containers(["web", "web-2"]);
+        let command = Command::new("docker create --name=web nginx", OUTPUT, ExitCode(125));
+        assert_eq!(
+            regular_corrections(command, &session_metadata),
+            vec![
+                "docker rm -f web && docker create --name=web nginx",
+                "docker create --name=web-3 nginx"
+            ]
+        );
+    }
+}
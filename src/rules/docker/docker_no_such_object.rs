@@ -0,0 +1,155 @@
+use crate::rules::docker::image::{find_in_command, split_tag};
+use crate::rules::util::{get_single_closest_match, new_commands_from_suggestions};
+use crate::rules::Rule;
+use crate::{default_rule_id, Command, RuleCorrection, SessionMetadata};
+use itertools::Itertools;
+use lazy_static::lazy_static;
+use regex::Regex;
+
+lazy_static! {
+    // e.g. `Error response from daemon: No such container: webb`.
+    static ref CONTAINER_RE: Regex = Regex::new(r"(?i)no such container: ?(\S+)").unwrap();
+    // e.g. `Error response from daemon: No such image: ngnix:latest` or
+    // `Unable to find image 'ngnix:latest' locally` followed by the pull failing (otherwise docker
+    // just pulled the image).
+    static ref IMAGE_RE: Regex = Regex::new(
+        r"(?is)no such image: ?(\S+)|unable to find image '([^']+)' locally.*(?:pull access denied|repository does not exist|manifest unknown)"
+    )
+    .unwrap();
+    // e.g. `Error: No such object: webb` from `docker inspect`, which takes either.
+    static ref OBJECT_RE: Regex = Regex::new(r"(?i)no such object: ?(\S+)").unwrap();
+}
+
+/*
+Corrects a misspelled container or image, e.g. `docker logs webb` => `docker logs web`, using
+the containers and images the client gave us (see `SessionMetadata::set_docker_containers`
+and `SessionMetadata::set_docker_images`).
+*/
+pub(crate) struct DockerNoSuchObject;
+impl Rule for DockerNoSuchObject {
+    default_rule_id!(DockerNoSuchObject);
+
+    fn matches(&self, command: &Command, _session_metadata: &SessionMetadata) -> bool {
+        [&*CONTAINER_RE, &*IMAGE_RE, &*OBJECT_RE]
+            .iter()
+            .any(|re| re.is_match(command.output))
+    }
+
+    fn generate_command_corrections<'a>(
+        &self,
+        command: &'a Command,
+        session_metadata: &'a SessionMetadata,
+    ) -> Option<Vec<RuleCorrection<'a>>> {
+        let captured = |re: &Regex| {
+            let captures = re.captures(command.output)?;
+            Some(captures.get(1).or_else(|| captures.get(2))?.as_str())
+        };
+        let containers = session_metadata.docker_containers.iter().copied().sorted();
+
+        let (unknown, candidates) = if let Some(container) = captured(&CONTAINER_RE) {
+            (container, containers.collect_vec())
+        } else if let Some(image) = captured(&IMAGE_RE) {
+            let unknown = find_in_command(command.input_parts(), image)?;
+            (unknown, images(unknown, session_metadata))
+        } else {
+            let unknown = captured(&OBJECT_RE)?;
+            let images = images(unknown, session_metadata);
+            (unknown, containers.chain(images).collect_vec())
+        };
+
+        let matcher = session_metadata.matcher(self.id());
+        let candidates = candidates.into_iter().filter(|name| *name != unknown);
+        let fix = get_single_closest_match(unknown, candidates, matcher)?;
+        new_commands_from_suggestions([fix], command.input_parts(), unknown)
+    }
+}
+
+/// The images to match `unknown` against: with their tags if `unknown` has one.
+fn images<'a>(unknown: &str, session_metadata: &SessionMetadata<'a>) -> Vec<&'a str> {
+    let has_tag = split_tag(unknown).1.is_some();
+    session_metadata
+        .docker_images
+        .iter()
+        .map(|image| if has_tag { image } else { split_tag(image).0 })
+        .sorted()
+        .dedup()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::regular_corrections;
+    use crate::{Command, ExitCode, SessionMetadata};
+
+    fn session_metadata() -> SessionMetadata<'static> {
+        let mut session_metadata = SessionMetadata::new();
+        session_metadata.set_docker_containers(["web", "db", "worker"]);
+        session_metadata.set_docker_images(["nginx:1.25", "nginx:latest", "postgres:16"]);
+        session_metadata
+    }
+
+    #[test]
+    fn test_no_such_container() {
+        let command = Command::new(
+            "docker logs -f webb",
+            "Error response from daemon: No such container: webb",
+            ExitCode(1),
+        );
+        assert_eq!(
+            regular_corrections(command, &session_metadata()),
+            vec!["docker logs -f web"]
+        );
+    }
+
+    #[test]
+    fn test_no_such_image() {
+        let command = Command::new(
+            "docker run -d ngnix",
+            "Unable to find image 'ngnix:latest' locally
+docker: Error response from daemon: pull access denied for ngnix, repository does not exist or may require 'docker login'.",
+            ExitCode(125),
+        );
+        assert_eq!(
+            regular_corrections(command, &session_metadata())[0],
+            "docker run -d nginx"
+        );
+
+        let command = Command::new(
+            "docker image inspect postgres:61",
+            "Error response from daemon: No such image: postgres:61",
+            ExitCode(1),
+        );
+        assert_eq!(
+            regular_corrections(command, &session_metadata()),
+            vec!["docker image inspect postgres:16"]
+        );
+    }
+
+    #[test]
+    fn test_pulled_image() {
+        // The image was pulled, and the run failed for another reason.
+        let command = Command::new(
+            "docker run -d -p 8080:80 ngnix",
+            "Unable to find image 'ngnix:latest' locally
+latest: Pulling from library/ngnix
+Status: Downloaded newer image for ngnix:latest
+docker: Error response from daemon: driver failed programming external connectivity on endpoint eager_hopper (3f1c): Bind for 0.0.0.0:8080 failed: port is already allocated.",
+            ExitCode(125),
+        );
+        assert!(!regular_corrections(command, &session_metadata())
+            .contains(&"docker run -d -p 8080:80 nginx".to_owned()));
+    }
+
+    #[test]
+    fn test_no_such_object() {
+        let command = Command::new(
+            "docker inspect wroker",
+            "[]\nError: No such object: wroker",
+            ExitCode(1),
+        );
+        assert_eq!(
+            regular_corrections(command, &session_metadata()),
+            vec!["docker inspect worker"]
+        );
+    }
+}
@@ -0,0 +1,138 @@
+use crate::rules::Rule;
+use crate::{default_rule_id, Command, RuleCorrection, SessionMetadata};
+use itertools::Itertools;
+use lazy_static::lazy_static;
+use regex::Regex;
+
+lazy_static! {
+    // e.g. `Bind for 0.0.0.0:8080 failed: port is already allocated` or
+    // `Error starting userland proxy: listen tcp4 0.0.0.0:5432: bind: address already in use`.
+    static ref RE: Regex = Regex::new(
+        r"(?i)bind for \S*:(\d+) failed: port is already allocated|listen tcp[46]? \S*:(\d+): bind: address already in use"
+    )
+    .unwrap();
+}
+
+/*
+Publishes a container on the next host port when the one in the `-p` mapping is taken, e.g.
+`docker run -p 8080:80 nginx` => `docker run -p 8081:80 nginx`. Ports the command already
+publishes are skipped. We can't tell which other ports are free, so this is only a guess.
+*/
+pub(crate) struct DockerPortAllocated;
+impl Rule for DockerPortAllocated {
+    default_rule_id!(DockerPortAllocated);
+
+    fn matches(&self, command: &Command, _session_metadata: &SessionMetadata) -> bool {
+        RE.is_match(command.output)
+    }
+
+    fn generate_command_corrections<'a>(
+        &self,
+        command: &'a Command,
+        _session_metadata: &'a SessionMetadata,
+    ) -> Option<Vec<RuleCorrection<'a>>> {
+        let captures = RE.captures(command.output)?;
+        let port = captures.get(1).or_else(|| captures.get(2))?.as_str();
+
+        let mappings = port_mappings(command.input_parts()).collect_vec();
+        let used = mappings
+            .iter()
+            .filter_map(|mapping| mapping.host_port()?.parse::<u16>().ok())
+            .collect_vec();
+        let next_port = (port.parse::<u16>().ok()?..=u16::MAX)
+            .skip(1)
+            .find(|port| !used.contains(port))?;
+
+        let mapping = mappings
+            .iter()
+            .find(|mapping| mapping.host_port() == Some(port))?;
+        let mut replacement = command.input_parts().to_vec();
+        replacement[mapping.index] = mapping.with_host_port(&next_port.to_string());
+        Some(vec![replacement.into()])
+    }
+}
+
+/// A `-p`/`--publish` mapping, e.g. `127.0.0.1:8080:80/tcp`.
+struct PortMapping<'c> {
+    /// The index of the command part with the mapping.
+    index: usize,
+    /// What comes before the mapping in its part, e.g. `--publish=`.
+    prefix: &'c str,
+    mapping: &'c str,
+}
+
+impl PortMapping<'_> {
+    fn components(&self) -> Vec<&str> {
+        self.mapping.split(':').collect()
+    }
+
+    /// The host port, if the mapping has one (`-p 80` publishes on a random port).
+    fn host_port(&self) -> Option<&str> {
+        let components = self.components();
+        (components.len() >= 2).then(|| components[components.len() - 2])
+    }
+
+    fn with_host_port(&self, host_port: &str) -> String {
+        let mut components = self.components();
+        let host_index = components.len() - 2;
+        components[host_index] = host_port;
+        format!("{}{}", self.prefix, components.join(":"))
+    }
+}
+
+fn port_mappings(input_parts: &[String]) -> impl Iterator<Item = PortMapping<'_>> {
+    input_parts.iter().enumerate().filter_map(|(index, part)| {
+        if let Some(previous) = index.checked_sub(1).map(|i| input_parts[i].as_str()) {
+            if previous == "-p" || previous == "--publish" {
+                return Some(PortMapping {
+                    index,
+                    prefix: "",
+                    mapping: part,
+                });
+            }
+        }
+        ["--publish=", "-p=", "-p"].into_iter().find_map(|prefix| {
+            let mapping = part.strip_prefix(prefix)?;
+            (!mapping.is_empty()).then_some(PortMapping {
+                index,
+                prefix,
+                mapping,
+            })
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::basic_corrections;
+
+    #[test]
+    fn test_port_allocated() {
+        assert_eq!(
+            basic_corrections(
+                "docker run -d -p 8081:443 -p 8080:80 --name web nginx",
+                "docker: Error response from daemon: driver failed programming external connectivity on endpoint web (3f1c): Bind for 0.0.0.0:8080 failed: port is already allocated."
+            ),
+            vec!["docker run -d -p 8081:443 -p 8082:80 --name web nginx"]
+        );
+        assert_eq!(
+            basic_corrections(
+                "docker run --publish=127.0.0.1:5432:5432/tcp postgres",
+                "Error starting userland proxy: listen tcp4 127.0.0.1:5432: bind: address already in use."
+            ),
+            vec!["docker run --publish=127.0.0.1:5433:5432/tcp postgres"]
+        );
+    }
+
+    #[test]
+    fn test_no_mapping() {
+        // The ports are in the compose file.
+        assert_eq!(
+            basic_corrections(
+                "docker compose up",
+                "Error response from daemon: Bind for 0.0.0.0:8080 failed: port is already allocated"
+            ),
+            Vec::<String>::new()
+        );
+    }
+}
@@ -0,0 +1,44 @@
+//! Image references, e.g. `ghcr.io/owner/app:1.2`.
+
+/// Splits an image reference into its repository and tag, e.g. `nginx:1.25` => (`nginx`, `1.25`).
+/// A colon before the last `/` is a registry port (`localhost:5000/app`), not a tag.
+pub(crate) fn split_tag(image: &str) -> (&str, Option<&str>) {
+    let image = image
+        .split_once('@')
+        .map_or(image, |(image, _digest)| image);
+    match image.rsplit_once(':') {
+        Some((repository, tag)) if !tag.contains('/') => (repository, Some(tag)),
+        _ => (image, None),
+    }
+}
+
+/// The part of the command that refers to `image`, which docker reports with the implicit
+/// `:latest` tag (e.g. `ngnix:latest` for `docker run ngnix`).
+pub(crate) fn find_in_command<'c>(input_parts: &'c [String], image: &str) -> Option<&'c str> {
+    let untagged = image.strip_suffix(":latest");
+    input_parts
+        .iter()
+        .skip(1)
+        .find(|part| *part == image || Some(part.as_str()) == untagged)
+        .map(String::as_str)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::split_tag;
+
+    #[test]
+    fn test_split_tag() {
+        assert_eq!(split_tag("nginx"), ("nginx", None));
+        assert_eq!(split_tag("nginx:1.25"), ("nginx", Some("1.25")));
+        assert_eq!(
+            split_tag("localhost:5000/app"),
+            ("localhost:5000/app", None)
+        );
+        assert_eq!(
+            split_tag("localhost:5000/app:dev"),
+            ("localhost:5000/app", Some("dev"))
+        );
+        assert_eq!(split_tag("app:dev@sha256:abc"), ("app", Some("dev")));
+    }
+}
@@ -1,20 +1,32 @@
 use crate::rules::docker::{
-    docker_image_rm::DockerImageRm, docker_login::DockerLogin, docker_no_command::DockerNoCommand,
+    docker_daemon_not_running::DockerDaemonNotRunning, docker_image_rm::DockerImageRm,
+    docker_login::DockerLogin, docker_manifest_unknown::DockerManifestUnknown,
+    docker_name_conflict::DockerNameConflict, docker_no_such_object::DockerNoSuchObject,
+    docker_port_allocated::DockerPortAllocated,
 };
 use crate::rules::Rule;
 
 use super::CommandGroup;
 
+mod docker_daemon_not_running;
 mod docker_image_rm;
 mod docker_login;
-mod docker_no_command;
+mod docker_manifest_unknown;
+mod docker_name_conflict;
+mod docker_no_such_object;
+mod docker_port_allocated;
+mod image;
 
 pub(crate) fn command_group() -> CommandGroup {
     CommandGroup {
         command_names: &["docker"],
         rules: vec![
-            DockerNoCommand.to_arc(),
             DockerImageRm.to_arc(),
+            DockerDaemonNotRunning.to_arc(),
+            DockerPortAllocated.to_arc(),
+            DockerNameConflict.to_arc(),
+            DockerManifestUnknown.to_arc(),
+            DockerNoSuchObject.to_arc(),
             DockerLogin.to_arc(),
         ],
     }
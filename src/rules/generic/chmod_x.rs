@@ -34,9 +34,8 @@ impl Rule for ChmodX {
 }
 
 #[cfg(test)]
-// TODO: add better testing utilities to test rules in isolation.
-// Below, we need to use `contains` to avoid equality tests because
-// the `sudo` rule also surfaces corrections.
+// Note: `basic_corrections` applies every rule, so the `sudo` rule's corrections show up below.
+// Use `test_utils::rule_corrections` (or a fixture) to test this rule in isolation.
 mod tests {
     use crate::test_utils::basic_corrections;
 
@@ -0,0 +1,143 @@
+use crate::rules::Rule;
+use crate::{default_rule_id, Command, RuleCorrection, SessionMetadata};
+use lazy_static::lazy_static;
+use regex::Regex;
+
+lazy_static! {
+    // `user@host:~$` or `(venv) $` (or both), `PS C:\>`, `In [1]:` or a bare `#`, `%` or `>`.
+    // A bare `$` is left to `LeadingShellPrompt`.
+    static ref PROMPT_RE: Regex = Regex::new(
+        r"^(?:(?:\([^)\s]+\)\s*)?[\w.-]+@[\w.-]+(?::\S*?)?\s?[$#%>]|\([^)\s]+\)\s*[$#%>]|PS(?: [^>]*)?>|In \[\d+\]:|[#%>])\s+(\S.*)$"
+    )
+    .unwrap();
+}
+
+/*
+Fixes commands that were pasted from docs or chat, which often contain characters that look
+right but aren't what the shell expects (unicode dashes, smart quotes, non-breaking spaces,
+zero-width characters or a `\r`), or start with a shell prompt.
+*/
+pub(crate) struct CopyPaste;
+impl Rule for CopyPaste {
+    default_rule_id!(CopyPaste);
+
+    fn matches(&self, command: &Command, _session_metadata: &SessionMetadata) -> bool {
+        command.input.chars().any(|c| sanitize(c).is_some()) || PROMPT_RE.is_match(command.input)
+    }
+
+    fn generate_command_corrections<'a>(
+        &self,
+        command: &'a Command,
+        _session_metadata: &'a SessionMetadata,
+    ) -> Option<Vec<RuleCorrection<'a>>> {
+        let sanitized = command
+            .input
+            .chars()
+            .fold(String::new(), |mut sanitized, c| {
+                match sanitize(c) {
+                    Some(replacement) => sanitized.push_str(replacement),
+                    None => sanitized.push(c),
+                }
+                sanitized
+            });
+        let sanitized = sanitized.trim();
+
+        let without_prompt = PROMPT_RE
+            .captures(sanitized)
+            .and_then(|captures| captures.get(1))
+            .map_or(sanitized, |command| command.as_str());
+        Some(vec![without_prompt.to_owned().into()])
+    }
+}
+
+/// What to replace a character with, if it's one that commonly sneaks in when copy-pasting.
+fn sanitize(c: char) -> Option<&'static str> {
+    match c {
+        // Em dashes are usually a `--` that was "smartened" by a word processor.
+        '\u{2014}' => Some("--"),
+        '\u{2010}'..='\u{2013}' | '\u{2015}' | '\u{2212}' => Some("-"),
+        '\u{2018}'..='\u{201B}' => Some("'"),
+        '\u{201C}'..='\u{201F}' => Some("\""),
+        '\u{00A0}' | '\u{2000}'..='\u{200A}' | '\u{202F}' | '\u{205F}' | '\u{3000}' => Some(" "),
+        '\u{200B}'..='\u{200D}' | '\u{2060}' | '\u{FEFF}' | '\u{00AD}' | '\r' => Some(""),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::basic_corrections;
+
+    #[test]
+    fn test_unicode_characters() {
+        assert_eq!(
+            basic_corrections("ls –la", "ls: cannot access '–la'"),
+            vec!["ls -la"]
+        );
+        assert_eq!(
+            basic_corrections("cargo —version", "error: unexpected argument"),
+            vec!["cargo --version"]
+        );
+        assert_eq!(
+            basic_corrections("git commit -m “fix tests”", "error: pathspec"),
+            vec!["git commit -m \"fix tests\""]
+        );
+        assert_eq!(
+            basic_corrections("echo ‘hi’", "some error"),
+            vec!["echo 'hi'"]
+        );
+        assert_eq!(
+            basic_corrections("git\u{a0}status", "command not found: git status"),
+            vec!["git status"]
+        );
+        assert_eq!(
+            basic_corrections(
+                "git sta\u{200b}tus",
+                "git: 'sta\u{200b}tus' is not a git command"
+            ),
+            vec!["git status"]
+        );
+        assert_eq!(
+            basic_corrections("ls\r -l", "command not found"),
+            vec!["ls -l"]
+        );
+    }
+
+    #[test]
+    fn test_prompts() {
+        for input in [
+            "> git status",
+            "% git status",
+            "PS C:\\> git status",
+            "PS> git status",
+            "user@host:~$ git status",
+            "root@my-server:/var/www# git status",
+            "(venv) $ git status",
+            "(base) user@host:~/src$ git status",
+            "In [1]: git status",
+        ] {
+            assert_eq!(
+                basic_corrections(input, "command not found"),
+                vec!["git status"],
+                "{input}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_nothing_to_sanitize() {
+        assert_eq!(
+            basic_corrections("git stats", "some error"),
+            Vec::<String>::new()
+        );
+        assert_eq!(
+            basic_corrections("echo 'a > b'", "some error"),
+            Vec::<String>::new()
+        );
+        // Handled by LeadingShellPrompt.
+        assert_eq!(
+            basic_corrections("$ git status", "some error"),
+            Vec::<String>::new()
+        );
+    }
+}
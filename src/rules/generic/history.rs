@@ -33,7 +33,8 @@ impl Rule for History {
         session_metadata: &'a SessionMetadata,
     ) -> Option<Vec<RuleCorrection<'a>>> {
         let history_entries = get_history_entries_excluding_command(command, session_metadata);
-        let fix = get_single_closest_match(command.input, history_entries)?;
+        let matcher = session_metadata.matcher(self.id());
+        let fix = get_single_closest_match(command.input, history_entries, matcher)?;
         Some(vec![fix.into()])
     }
 }
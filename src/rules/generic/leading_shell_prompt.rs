@@ -4,7 +4,7 @@ use lazy_static::lazy_static;
 use regex::Regex;
 
 lazy_static! {
-    static ref RE: Regex = Regex::new("[\\s]*\\$[\\s]*(\\S.*)").unwrap();
+    static ref RE: Regex = Regex::new("^[\\s]*\\$[\\s]*(\\S.*)").unwrap();
 }
 
 /*
@@ -25,7 +25,7 @@ impl Rule for LeadingShellPrompt {
         command: &'a Command,
         _session_metadata: &'a SessionMetadata,
     ) -> Option<Vec<RuleCorrection<'a>>> {
-        Some(vec![command.input_parts()[1..].into()])
+        Some(vec![command.input_parts().get(1..)?.into()])
     }
 }
 
@@ -0,0 +1,290 @@
+use crate::rules::Rule;
+use crate::{
+    default_rule_id, Command, PlaceholderKind, RuleCorrection, SessionMetadata, TemplatePart,
+};
+use itertools::Itertools;
+use lazy_static::lazy_static;
+use regex::Regex;
+
+lazy_static! {
+    // clap: `error: the following required arguments were not provided:\n  <NAME>\n  <PATH>`
+    static ref CLAP_RE: Regex =
+        Regex::new(r"(?i)the following required arguments were not provided:((?:\s+<[^>\n]+>)+)").unwrap();
+    static ref ANGLE_BRACKETS_RE: Regex = Regex::new(r"<([^>\s]+)>").unwrap();
+    // argparse: `error: the following arguments are required: src, dest`
+    static ref ARGPARSE_RE: Regex =
+        Regex::new(r"(?i)the following arguments are required: ([^\n]+)").unwrap();
+    // click: `Error: Missing argument 'SRC'.`
+    static ref CLICK_RE: Regex = Regex::new(r"(?i)Missing argument '([^']+)'").unwrap();
+    // GNU coreutils: `cp: missing destination file operand after 'file'` or `mkdir: missing operand`
+    static ref GNU_RE: Regex = Regex::new(r"(?i)missing (?:(\w+) )?(?:\w+ )?operand").unwrap();
+    // cobra: `Error: accepts 1 arg(s), received 0` or `requires at least 2 arg(s), only received 1`
+    static ref COBRA_RE: Regex =
+        Regex::new(r"(?i)(?:accepts|requires at least) (\d+) arg\(s\), (?:only )?received (\d+)").unwrap();
+
+    // Errors that come with a usage line listing the arguments, e.g. git's
+    // `fatal: You must specify a repository to clone.` and `usage: git clone [<options>] [--] <repo> [<dir>]`.
+    static ref USAGE_ERROR_RE: Regex =
+        Regex::new(r"(?i)must specify|required|missing|not enough arguments|too few arguments").unwrap();
+    static ref USAGE_RE: Regex = Regex::new(r"(?im)^\s*usage:\s*(.+)$").unwrap();
+}
+
+/*
+Adds the arguments a command is missing as placeholders, e.g. `git clone` => `git clone <repo>`
+or `cp file` => `cp file <destination>`. The names come from the error (clap, argparse, click,
+GNU coreutils) or from the usage line printed with it. The correction can't be run as is:
+it's marked as needing input, so the user can fill in the placeholders first.
+*/
+pub(crate) struct MissingArgument;
+impl Rule for MissingArgument {
+    default_rule_id!(MissingArgument);
+
+    fn matches(&self, command: &Command, _session_metadata: &SessionMetadata) -> bool {
+        [&*CLAP_RE, &*ARGPARSE_RE, &*CLICK_RE, &*GNU_RE, &*COBRA_RE]
+            .iter()
+            .any(|re| re.is_match(command.output))
+            || (USAGE_ERROR_RE.is_match(command.output) && USAGE_RE.is_match(command.output))
+    }
+
+    fn generate_command_corrections<'a>(
+        &self,
+        command: &'a Command,
+        _session_metadata: &'a SessionMetadata,
+    ) -> Option<Vec<RuleCorrection<'a>>> {
+        let missing = missing_arguments(command)?;
+        if missing.is_empty() {
+            return None;
+        }
+
+        let parts = command
+            .input_parts()
+            .iter()
+            .map(|part| TemplatePart::from(part.as_str()))
+            .chain(missing.into_iter().map(|name| {
+                let kind = kind_of(&name);
+                TemplatePart::placeholder(name, kind)
+            }))
+            .collect();
+        Some(vec![RuleCorrection::Template(parts)])
+    }
+}
+
+/// The names of the missing arguments, in the order they should be added.
+fn missing_arguments(command: &Command) -> Option<Vec<String>> {
+    let output = command.output;
+
+    if let Some(captures) = CLAP_RE.captures(output) {
+        return Some(
+            ANGLE_BRACKETS_RE
+                .captures_iter(&captures[1])
+                .map(|captures| captures[1].to_owned())
+                .collect(),
+        );
+    }
+    if let Some(captures) = ARGPARSE_RE.captures(output) {
+        return Some(
+            captures[1]
+                .split(',')
+                .map(str::trim)
+                .filter(|name| !name.is_empty())
+                .map(str::to_owned)
+                .collect(),
+        );
+    }
+    if let Some(captures) = CLICK_RE.captures(output) {
+        return Some(vec![captures[1].to_owned()]);
+    }
+    if let Some(captures) = GNU_RE.captures(output) {
+        let name = captures.get(1).map_or("operand", |name| name.as_str());
+        return Some(vec![name.to_owned()]);
+    }
+    if let Some(missing) = missing_from_usage(command) {
+        return Some(missing);
+    }
+
+    let captures = COBRA_RE.captures(output)?;
+    let expected: usize = captures[1].parse().ok()?;
+    let received: usize = captures[2].parse().ok()?;
+    Some(vec!["arg".to_owned(); expected.saturating_sub(received)])
+}
+
+/// The required arguments (`<repo>`, but not `[<dir>]`) from the usage line that the user
+/// didn't provide.
+fn missing_from_usage(command: &Command) -> Option<Vec<String>> {
+    if !USAGE_ERROR_RE.is_match(command.output) {
+        return None;
+    }
+    let usage = USAGE_RE.captures(command.output)?.get(1)?.as_str();
+    let required = without_optional(usage);
+
+    // The usage starts with the command itself, e.g. `git clone`.
+    let input_parts = command.input_parts();
+    let command_words = required
+        .split_whitespace()
+        .take_while(|word| !word.starts_with(['<', '-']))
+        .count();
+    if command_words == 0
+        || input_parts.len() < command_words
+        || input_parts[..command_words]
+            .iter()
+            .zip(required.split_whitespace())
+            .any(|(part, word)| part != word)
+    {
+        return None;
+    }
+
+    let provided = input_parts[command_words..]
+        .iter()
+        .filter(|part| !part.starts_with('-'))
+        .count();
+    Some(
+        ANGLE_BRACKETS_RE
+            .captures_iter(&required)
+            .map(|captures| captures[1].to_owned())
+            .skip(provided)
+            .collect(),
+    )
+}
+
+/// Guesses the kind of an argument from its name, e.g. `<repo>` or `DEST_DIR`.
+fn kind_of(name: &str) -> PlaceholderKind {
+    let name = name.to_lowercase();
+    if ["url", "repo"].iter().any(|url| name.contains(url)) {
+        PlaceholderKind::Url
+    } else if ["path", "file", "dir", "dest", "src", "source"]
+        .iter()
+        .any(|path| name.contains(path))
+    {
+        PlaceholderKind::Path
+    } else if name.contains("branch") {
+        PlaceholderKind::Branch
+    } else if name.contains("message") || name == "msg" {
+        PlaceholderKind::Message
+    } else {
+        PlaceholderKind::Argument
+    }
+}
+
+/// Removes the optional parts (in brackets) of a usage line.
+fn without_optional(usage: &str) -> String {
+    let mut depth = 0;
+    usage
+        .chars()
+        .filter(|c| {
+            match c {
+                '[' => depth += 1,
+                ']' => {
+                    depth -= 1;
+                    return false;
+                }
+                _ => {}
+            }
+            depth == 0
+        })
+        .collect::<String>()
+        .split_whitespace()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::basic_corrections;
+    use crate::{
+        correct_command, Command, ExitCode, Placeholder, PlaceholderKind, SessionMetadata,
+    };
+
+    #[test]
+    fn test_git_usage() {
+        assert_eq!(
+            basic_corrections(
+                "git clone",
+                "fatal: You must specify a repository to clone.
+
+usage: git clone [<options>] [--] <repo> [<dir>]
+
+    -v, --verbose         be more verbose"
+            ),
+            vec!["git clone <repo>"]
+        );
+    }
+
+    #[test]
+    fn test_gnu() {
+        assert_eq!(
+            basic_corrections(
+                "cp file",
+                "cp: missing destination file operand after 'file'\nTry 'cp --help' for more information."
+            ),
+            vec!["cp file <destination>"]
+        );
+        assert_eq!(
+            basic_corrections(
+                "mkdir -p",
+                "mkdir: missing operand\nTry 'mkdir --help' for more information."
+            ),
+            vec!["mkdir -p <operand>"]
+        );
+    }
+
+    #[test]
+    fn test_clap_argparse_click() {
+        assert_eq!(
+            basic_corrections(
+                "mytool run",
+                "error: the following required arguments were not provided:
+  <NAME>
+  <PATH>
+
+Usage: mytool run <NAME> <PATH>"
+            ),
+            vec!["mytool run <NAME> <PATH>"]
+        );
+        assert_eq!(
+            basic_corrections(
+                "tool.py --verbose",
+                "usage: tool.py [-h] [--verbose] src dest\ntool.py: error: the following arguments are required: src, dest"
+            ),
+            vec!["tool.py --verbose <src> <dest>"]
+        );
+        assert_eq!(
+            basic_corrections(
+                "cli copy",
+                "Usage: cli copy [OPTIONS] SRC\nTry 'cli copy --help' for help.\n\nError: Missing argument 'SRC'."
+            ),
+            vec!["cli copy <SRC>"]
+        );
+    }
+
+    #[test]
+    fn test_cobra() {
+        assert_eq!(
+            basic_corrections("gh repo clone", "accepts 1 arg(s), received 0"),
+            vec!["gh repo clone <arg>"]
+        );
+    }
+
+    #[test]
+    fn test_placeholders() {
+        let command = Command::new(
+            "cp 'my file'",
+            "cp: missing destination file operand after 'my file'",
+            ExitCode(1),
+        );
+        let corrections = correct_command(command, &SessionMetadata::new());
+        let [correction] = corrections.as_slice() else {
+            panic!("expected a single correction");
+        };
+        assert_eq!(correction.command, "cp 'my file' <destination>");
+        assert!(correction.needs_input());
+        assert_eq!(
+            correction.placeholders,
+            vec![Placeholder {
+                name: "destination".to_owned(),
+                kind: PlaceholderKind::Path,
+                default: None,
+                span: 13..26
+            }]
+        );
+        assert_eq!(&correction.command[13..26], "<destination>");
+    }
+}
@@ -1,29 +1,46 @@
 use crate::rules::generic::{
-    cd_parent::CdParent, chmod_x::ChmodX, leading_shell_prompt::LeadingShellPrompt,
+    cd_parent::CdParent, chmod_x::ChmodX, copy_paste::CopyPaste,
+    leading_shell_prompt::LeadingShellPrompt, missing_argument::MissingArgument,
     no_command::NoCommand, python::Python, repetition::Repetition, sudo::Sudo,
+    uninitialized_submodule::UninitializedSubmodule, unknown_option::UnknownOption,
+    whitespace::Whitespace, wrong_keyboard_layout::WrongKeyboardLayout,
 };
 use crate::rules::Rule;
 use std::sync::Arc;
 
 mod cd_parent;
 mod chmod_x;
+mod copy_paste;
+// The history rule is disabled below, so nothing uses it for now.
+#[allow(dead_code)]
 mod history;
 mod leading_shell_prompt;
+mod missing_argument;
 mod no_command;
 mod python;
 mod repetition;
 mod sudo;
+mod uninitialized_submodule;
+mod unknown_option;
+mod whitespace;
+mod wrong_keyboard_layout;
 
 pub(crate) fn rules() -> Vec<Arc<dyn Rule>> {
     vec![
         ChmodX.to_arc(),
         LeadingShellPrompt.to_arc(),
+        CopyPaste.to_arc(),
+        WrongKeyboardLayout.to_arc(),
         Repetition.to_arc(),
         Python.to_arc(),
         CdParent.to_arc(),
         Sudo.to_arc(),
+        UninitializedSubmodule.to_arc(),
         // TODO: disabling history rule until we can pass down more info
         // History.to_arc(),
+        UnknownOption.to_arc(),
+        MissingArgument.to_arc(),
+        Whitespace.to_arc(),
         NoCommand.to_arc(),
     ]
 }
@@ -12,7 +12,7 @@ impl Rule for NoCommand {
     fn matches(&self, command: &Command, session_metadata: &SessionMetadata) -> bool {
         // TODO: use a execute_command callback to just check `which`
         // Checking output is too brittle since it'll vary from shell to shell
-        command.input_parts().first().map_or(false, |command_name| {
+        command.input_parts().first().is_some_and(|command_name| {
             command.exit_code.raw() == 127
                 && !session_metadata.is_top_level_command(command_name.as_str())
         })
@@ -30,7 +30,8 @@ impl Rule for NoCommand {
 
         // Get a match from the top level commands
         let top_level_commands = session_metadata.top_level_commands().collect_vec();
-        let top_level_command_fix = get_single_closest_match(to_fix, top_level_commands);
+        let matcher = session_metadata.matcher(self.id());
+        let top_level_command_fix = get_single_closest_match(to_fix, top_level_commands, matcher);
 
         // Get a match from the shell history. But don't try to match against
         // things that aren't even top level commands.
@@ -38,7 +39,7 @@ impl Rule for NoCommand {
             .top_level_commands_from_history()
             .filter(|s| *s != to_fix && session_metadata.is_top_level_command(s))
             .collect_vec();
-        let history_command_fix = get_single_closest_match(to_fix, history_commands);
+        let history_command_fix = get_single_closest_match(to_fix, history_commands, matcher);
 
         // Favor the history match over the top level command match. We use
         // the history as an ordering trick to suggest something that's more relevant
@@ -50,6 +51,8 @@ impl Rule for NoCommand {
 
 #[cfg(test)]
 mod tests {
+    use crate::keyboard::Layout;
+    use crate::similarity::{DamerauLevenshtein, DifflibRatio, Matcher};
     use crate::{test_utils::regular_corrections, Command, SessionMetadata};
 
     const EXECUTABLES: &[&str] = &["git", "cargo"];
@@ -122,4 +125,35 @@ mod tests {
             vec!["git commit", "gt commit"]
         );
     }
+
+    #[test]
+    fn test_with_matcher() {
+        let command = Command::new("gti commit", "command not found", 127.into());
+        let mut metadata = SessionMetadata::new();
+        metadata.set_executables(["git", "cargo", "tig"]);
+        metadata.set_matcher(Matcher::new(DamerauLevenshtein, 0.6));
+
+        assert_eq!(regular_corrections(command, &metadata), vec!["git commit"]);
+    }
+
+    #[test]
+    fn test_with_keyboard_layout() {
+        // `cat` and `cut` are as close to `cit`, but "i" is next to "u" (and not "a").
+        let command = Command::new("cit file", "command not found", 127.into());
+        let mut metadata = SessionMetadata::new();
+        metadata.set_executables(["cat", "cut"]);
+        metadata.set_keyboard_layout(Layout::Qwerty);
+
+        assert_eq!(regular_corrections(command, &metadata), vec!["cut file"]);
+    }
+
+    #[test]
+    fn test_with_rule_matcher() {
+        let command = Command::new("gitt checkout", "command not found", 127.into());
+        let mut metadata = SessionMetadata::new();
+        metadata.set_executables(EXECUTABLES.iter().copied());
+        metadata.set_rule_matcher("NoCommand", Matcher::new(DifflibRatio, 1.0));
+
+        assert!(regular_corrections(command, &metadata).is_empty());
+    }
 }
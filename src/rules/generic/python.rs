@@ -13,7 +13,7 @@ impl Rule for Python {
         command
             .input_parts()
             .first()
-            .map_or(false, |p| p.ends_with(".py"))
+            .is_some_and(|p| p.ends_with(".py"))
             && (lowercase_output.contains("permission denied")
                 || lowercase_output.contains("command not found"))
     }
@@ -14,7 +14,7 @@ impl Rule for Repetition {
     // with subcommands that have the same name as the top-level command).
     fn matches(&self, command: &Command, _session_metadata: &SessionMetadata) -> bool {
         let input_parts = command.input_parts();
-        if let (Some(first_part), Some(second_part)) = (input_parts.get(0), input_parts.get(1)) {
+        if let (Some(first_part), Some(second_part)) = (input_parts.first(), input_parts.get(1)) {
             return first_part == second_part;
         }
         false
@@ -1,6 +1,7 @@
 /// Fixes error for commands that need to be run with "sudo".
 /// Note: this rule is not in the `sudo` directory, because it
 /// applies to rules that _don't_ start with `sudo`.
+use crate::risk::Risk;
 use crate::rules::Rule;
 use crate::{default_rule_id, Command, RuleCorrection, SessionMetadata};
 
@@ -47,6 +48,10 @@ impl Rule for Sudo {
         }
 
         let lowercase_output = command.lowercase_output();
+        // The remote refused the SSH key, e.g. `git@github.com: Permission denied (publickey).`
+        if lowercase_output.contains("permission denied (publickey") {
+            return false;
+        }
         PATTERNS
             .iter()
             .any(|pattern| lowercase_output.contains(pattern))
@@ -60,6 +65,10 @@ impl Rule for Sudo {
         let new_command = [&["sudo".to_owned()], command.input_parts()].concat();
         Some(vec![new_command.into()])
     }
+
+    fn risk(&self) -> Risk {
+        Risk::PrivilegeEscalation
+    }
 }
 
 #[cfg(test)]
@@ -73,4 +82,13 @@ mod tests {
             vec!["sudo rm file"]
         )
     }
+
+    #[test]
+    fn test_ssh_key_refused() {
+        assert!(basic_corrections(
+            "ssh git@github.com",
+            "git@github.com: Permission denied (publickey)."
+        )
+        .is_empty());
+    }
 }
@@ -0,0 +1,176 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::rules::git_repository::read_submodules;
+use crate::rules::Rule;
+use crate::{default_rule_id, Command, RuleCorrection, SessionMetadata};
+use lazy_static::lazy_static;
+use regex::Regex;
+
+lazy_static! {
+    // e.g. `Submodule path 'vendor/lib' not initialized` (with `Maybe you want to use
+    // 'update --init'?`) or `fatal: no submodule mapping found in .gitmodules for path 'vendor/lib'`.
+    static ref RE: Regex = Regex::new(
+        r"(?i)submodule path '[^']*' not initialized|no submodule mapping found in \.gitmodules"
+    )
+    .unwrap();
+    // Errors about a missing file, which is what an empty submodule directory leads to, e.g.
+    // `make: *** vendor/lib: No such file or directory.  Stop.`
+    static ref NOT_FOUND_RE: Regex = Regex::new(
+        r"(?i)no such file or directory|not found|cannot find|can't find|could not find|does not exist|cannot open|can't open|no rule to make target"
+    )
+    .unwrap();
+}
+
+const UPDATE: [&str; 3] = ["git", "submodule", "update"];
+const UPDATE_OPTIONS: &[&str] = &["--init", "--recursive"];
+
+/*
+Initializes and checks out submodules (`git submodule update --init --recursive`) when git says
+they aren't, or when a command fails on a submodule's directory that's still empty, e.g.
+`make` => `git submodule update --init --recursive && make`.
+*/
+pub(crate) struct UninitializedSubmodule;
+impl Rule for UninitializedSubmodule {
+    default_rule_id!(UninitializedSubmodule);
+
+    fn matches(&self, command: &Command, session_metadata: &SessionMetadata) -> bool {
+        if RE.is_match(command.output) {
+            return true;
+        }
+        // Only look for empty submodules on disk if something wasn't found.
+        NOT_FOUND_RE.is_match(command.output)
+            && (session_metadata.session_type.is_local()
+                && command.working_dir.is_some_and(|working_dir| {
+                    empty_submodules(working_dir)
+                        .iter()
+                        .any(|submodule| is_mentioned(submodule, working_dir, command.output))
+                }))
+    }
+
+    fn generate_command_corrections<'a>(
+        &self,
+        command: &'a Command,
+        _session_metadata: &'a SessionMetadata,
+    ) -> Option<Vec<RuleCorrection<'a>>> {
+        let input_parts = command.input_parts();
+        let update = UPDATE.iter().chain(UPDATE_OPTIONS).copied();
+
+        // `git submodule update` just needs the options.
+        if input_parts.starts_with(&UPDATE.map(str::to_owned)) {
+            let other_args = input_parts[UPDATE.len()..]
+                .iter()
+                .map(String::as_str)
+                .filter(|arg| !UPDATE_OPTIONS.contains(arg));
+            return Some(vec![update.chain(other_args).collect::<Vec<_>>().into()]);
+        }
+        Some(vec![RuleCorrection::and(
+            update.collect::<Vec<_>>(),
+            input_parts,
+        )])
+    }
+}
+
+fn empty_submodules(working_dir: &str) -> Vec<PathBuf> {
+    read_submodules(working_dir)
+        .into_iter()
+        .filter(|submodule| {
+            fs::read_dir(submodule).is_ok_and(|mut entries| entries.next().is_none())
+        })
+        .collect()
+}
+
+/// Whether a not-found error in the output mentions the submodule's path (relative to the working
+/// directory or absolute), or a path in the submodule.
+fn is_mentioned(submodule: &Path, working_dir: &str, output: &str) -> bool {
+    let relative = submodule.strip_prefix(working_dir).ok();
+    let paths = [Some(submodule), relative]
+        .into_iter()
+        .flatten()
+        .filter_map(Path::to_str)
+        .filter(|path| !path.is_empty())
+        .collect::<Vec<_>>();
+    output
+        .lines()
+        .filter(|line| NOT_FOUND_RE.is_match(line))
+        .any(|line| paths.iter().any(|path| contains_path(line, path)))
+}
+
+/// Whether the line contains the path as a whole, e.g. `vendor/lib` in `vendor/lib/Makefile`
+/// but not in `vendor/libs` or `other-vendor/lib`.
+fn contains_path(line: &str, path: &str) -> bool {
+    let is_path_char = |c: char| c.is_alphanumeric() || matches!(c, '_' | '-' | '.' | '/');
+    line.match_indices(path).any(|(start, _)| {
+        let before = &line[..start];
+        let after = &line[start + path.len()..];
+        let starts_path =
+            before.ends_with("./") || !before.chars().next_back().is_some_and(is_path_char);
+        // A trailing `.` ends the sentence, and a `/` is a path in the submodule.
+        let ends_path = match after.chars().next() {
+            None | Some('/') => true,
+            Some('.') => !after.chars().nth(1).is_some_and(is_path_char),
+            Some(c) => !is_path_char(c),
+        };
+        starts_path && ends_path
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use crate::test_utils::{basic_corrections, regular_corrections};
+    use crate::{Command, ExitCode, SessionMetadata};
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_git_submodule_update() {
+        assert_eq!(
+            basic_corrections(
+                "git submodule update --recursive",
+                "Submodule path 'vendor/lib' not initialized
+Maybe you want to use 'update --init'?"
+            ),
+            vec!["git submodule update --init --recursive"]
+        );
+    }
+
+    #[test]
+    fn test_empty_submodule_directory() {
+        let repo = tempdir().unwrap();
+        fs::create_dir_all(repo.path().join(".git")).unwrap();
+        fs::create_dir_all(repo.path().join("vendor/lib")).unwrap();
+        fs::write(
+            repo.path().join(".gitmodules"),
+            "[submodule \"lib\"]\n\tpath = vendor/lib\n\turl = https://github.com/org/lib.git\n",
+        )
+        .unwrap();
+
+        let output = "make: *** vendor/lib: No such file or directory.  Stop.";
+        let command = Command::new("make -C vendor/lib", output, ExitCode(2))
+            .set_working_dir(repo.path().to_str().unwrap());
+        assert_eq!(
+            regular_corrections(command, &SessionMetadata::new()),
+            vec!["git submodule update --init --recursive && make -C vendor/lib"]
+        );
+
+        // The path has to be a whole path in a not-found error.
+        for output in [
+            "make: *** vendor/libs: No such file or directory.  Stop.",
+            "make: *** vendor/lib: Is a directory.  Stop.",
+        ] {
+            let command = Command::new("make -C vendor/lib", output, ExitCode(2))
+                .set_working_dir(repo.path().to_str().unwrap());
+            assert_eq!(
+                regular_corrections(command, &SessionMetadata::new()),
+                Vec::<String>::new()
+            );
+        }
+
+        // Once checked out, the submodule isn't the problem.
+        fs::write(repo.path().join("vendor/lib/Makefile"), "").unwrap();
+        let command = Command::new("make -C vendor/lib", output, ExitCode(2))
+            .set_working_dir(repo.path().to_str().unwrap());
+        assert!(regular_corrections(command, &SessionMetadata::new()).is_empty());
+    }
+}
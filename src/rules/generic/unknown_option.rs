@@ -0,0 +1,259 @@
+use crate::rules::catalogue;
+use crate::rules::parse_help;
+use crate::rules::util::get_single_closest_match;
+use crate::rules::Rule;
+use crate::{default_rule_id, Command, RuleCorrection, SessionMetadata};
+use lazy_static::lazy_static;
+use regex::Regex;
+
+lazy_static! {
+    static ref UNKNOWN_OPTION_RES: Vec<Regex> = [
+        // GNU getopt: `ls: unrecognized option '--colour'`
+        r"(?i)unrecognized option:? '?(--?[^'\s]+)",
+        // argparse: `error: unrecognized arguments: --verbse`
+        r"(?i)unrecognized arguments?: (--?\S+)",
+        // clap 4: `error: unexpected argument '--relase' found`
+        r"(?i)unexpected argument '(--?[^']+)'",
+        // clap 3: `error: Found argument '--relase' which wasn't expected`
+        r"(?i)Found argument '(--?[^']+)' which wasn't expected",
+        // cobra: `Error: unknown flag: --namspace`
+        r"(?i)unknown flag: (--?\S+)",
+        // git: ``error: unknown option `ammend'``
+        r"(?i)unknown option:? [`']-*([^'`\s]+)'",
+        // click: `Error: No such option: --verbse`
+        r"(?i)No such option: (--?[^\s.]+)",
+        r"(?i)invalid option:? '?(--[^'\s]+)",
+    ]
+    .iter()
+    .map(|pattern| Regex::new(pattern).unwrap())
+    .collect();
+
+    // e.g. clap's `tip: a similar argument exists: '--release'`, git's ``did you mean `--amend` ``
+    // or click's `Did you mean --verbose?`.
+    static ref HINT_RE: Regex =
+        Regex::new(r#"(?i)(?:did you mean|a similar argument exists:)\s*[`'"]?(--?[\w-]+)"#).unwrap();
+}
+
+/*
+Corrects a mistyped option, e.g. `git commit --ammend` => `git commit --amend`.
+The tool's own hint is used if it has one. Otherwise, the closest option is picked from the
+options listed in the output (e.g. git prints the usage), the tool's help text or the catalogue.
+Only long options are corrected, since a single mistyped character can't be reliably fixed.
+*/
+pub(crate) struct UnknownOption;
+impl Rule for UnknownOption {
+    default_rule_id!(UnknownOption);
+
+    fn matches(&self, command: &Command, _session_metadata: &SessionMetadata) -> bool {
+        UNKNOWN_OPTION_RES
+            .iter()
+            .any(|re| re.is_match(command.output))
+    }
+
+    fn generate_command_corrections<'a>(
+        &self,
+        command: &'a Command,
+        session_metadata: &'a SessionMetadata,
+    ) -> Option<Vec<RuleCorrection<'a>>> {
+        let unknown = UNKNOWN_OPTION_RES
+            .iter()
+            .find_map(|re| re.captures(command.output)?.get(1))?
+            .as_str();
+        // GNU getopt includes the value, e.g. `--colour=always`.
+        let unknown = unknown.split('=').next()?;
+
+        // Find the part of the input with the unknown option, which may include a value (`--colour=always`).
+        // Some tools (e.g. git) leave out the dashes in their error.
+        let input_parts = command.input_parts();
+        let (index, option, value) = input_parts.iter().enumerate().find_map(|(i, part)| {
+            let (option, value) = match part.split_once('=') {
+                Some((option, value)) => (option, Some(value)),
+                None => (part.as_str(), None),
+            };
+            let is_unknown = option == unknown
+                || (option.starts_with("--") && option.trim_start_matches('-') == unknown);
+            is_unknown.then_some((i, option, value))
+        })?;
+        if !option.starts_with("--") {
+            return None;
+        }
+
+        let fix = match HINT_RE
+            .captures(command.output)
+            .and_then(|captures| captures.get(1))
+        {
+            Some(hint) => hint.as_str().to_owned(),
+            None => {
+                let known_options = known_options(command, session_metadata);
+                let matcher = session_metadata.matcher(self.id());
+                get_single_closest_match(
+                    option,
+                    known_options
+                        .iter()
+                        .map(String::as_str)
+                        .filter(|known| known.starts_with("--")),
+                    matcher,
+                )?
+                .to_owned()
+            }
+        };
+
+        // Replace the option in the input as is if we can, to keep the user's quoting.
+        if command.input.matches(option).count() == 1 {
+            return Some(vec![command.input.replacen(option, &fix, 1).into()]);
+        }
+
+        let mut fixed = input_parts.to_vec();
+        fixed[index] = match value {
+            Some(value) => format!("{fix}={value}"),
+            None => fix,
+        };
+        Some(vec![fixed.into()])
+    }
+}
+
+/// The options we know about for the command, from the usage in its output, its help text
+/// and the catalogue.
+fn known_options(command: &Command, session_metadata: &SessionMetadata) -> Vec<String> {
+    let mut known_options = parse_help(command.output).flags;
+
+    let input_parts = command.input_parts();
+    let Some(tool) = input_parts
+        .first()
+        .and_then(|command_name| catalogue::tool(command_name))
+    else {
+        if let Some(help) = input_parts
+            .first()
+            .and_then(|name| session_metadata.help(name))
+        {
+            known_options.extend(help.flags.iter().cloned());
+        }
+        return known_options;
+    };
+
+    let (spec, invocation) = tool.command_for(input_parts, tool.version(session_metadata));
+    if let Some(help) = session_metadata.help(&invocation) {
+        known_options.extend(help.flags.iter().cloned());
+    }
+    known_options.extend(spec.flags.iter().map(|flag| flag.to_string()));
+    // Top-level options can usually be passed to subcommands, too.
+    known_options.extend(tool.command.flags.iter().map(|flag| flag.to_string()));
+    known_options
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::{basic_corrections, regular_corrections};
+    use crate::{Command, ExitCode, SessionMetadata};
+
+    #[test]
+    fn test_gnu_getopt() {
+        assert_eq!(
+            basic_corrections(
+                "ls --colour",
+                "ls: unrecognized option '--colour'\nTry 'ls --help' for more information."
+            ),
+            vec!["ls --color"]
+        );
+        assert_eq!(
+            basic_corrections(
+                "grep --recurisve foo .",
+                "grep: unrecognized option '--recurisve'\nUsage: grep [OPTION]... PATTERNS [FILE]..."
+            ),
+            vec!["grep --recursive foo ."]
+        );
+        assert_eq!(
+            basic_corrections(
+                "ls --colour=always",
+                "ls: unrecognized option '--colour=always'"
+            ),
+            vec!["ls --color=always"]
+        );
+        assert_eq!(
+            basic_corrections(
+                "ls --colour=always 'a b' --colour=always",
+                "ls: unrecognized option '--colour=always'"
+            ),
+            vec!["ls --color=always 'a b' --colour=always"]
+        );
+    }
+
+    #[test]
+    fn test_clap() {
+        assert_eq!(
+            basic_corrections(
+                "cargo build --relase",
+                "error: unexpected argument '--relase' found
+
+  tip: a similar argument exists: '--release'
+
+Usage: cargo build --release"
+            ),
+            vec!["cargo build --release"]
+        );
+        // Without the hint, the catalogue is used.
+        assert_eq!(
+            basic_corrections(
+                "cargo build --relase",
+                "error: Found argument '--relase' which wasn't expected, or isn't valid in this context"
+            ),
+            vec!["cargo build --release"]
+        );
+    }
+
+    #[test]
+    fn test_git() {
+        assert_eq!(
+            basic_corrections(
+                "git commit --ammend",
+                "error: unknown option `ammend'
+usage: git commit [<options>] [--] <pathspec>...
+
+    -q, --quiet           suppress summary after successful commit
+    --amend               amend previous commit"
+            ),
+            vec!["git commit --amend"]
+        );
+        assert_eq!(
+            basic_corrections("git push --forec", "error: unknown option `forec'"),
+            vec!["git push --force"]
+        );
+    }
+
+    #[test]
+    fn test_cobra() {
+        assert_eq!(
+            basic_corrections(
+                "kubectl get pods --namspace kube-system",
+                "error: unknown flag: --namspace\nSee 'kubectl get --help' for usage."
+            ),
+            vec!["kubectl get pods --namespace kube-system"]
+        );
+    }
+
+    #[test]
+    fn test_argparse_with_help_text() {
+        let mut session_metadata = SessionMetadata::new();
+        session_metadata.set_help_text(
+            "mytool",
+            "usage: mytool [-h] [--verbose]\n\noptions:\n  -h, --help  show help\n  --verbose   be loud",
+        );
+        let command = Command::new(
+            "mytool --verbse",
+            "mytool: error: unrecognized arguments: --verbse",
+            ExitCode(2),
+        );
+        assert_eq!(
+            regular_corrections(command, &session_metadata),
+            vec!["mytool --verbose"]
+        );
+    }
+
+    #[test]
+    fn test_short_options() {
+        assert_eq!(
+            basic_corrections("ls -y", "ls: invalid option -- 'y'"),
+            Vec::<String>::new()
+        );
+    }
+}
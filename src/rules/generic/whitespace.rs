@@ -0,0 +1,176 @@
+use crate::rules::{flags, subcommands, Rule};
+use crate::{default_rule_id, Command, RuleCorrection, SessionMetadata};
+
+/*
+Fixes missing or extra whitespace, e.g.
+- `gitstatus` => `git status`, `ls-la` => `ls -la` and `cd..` => `cd ..`
+- `gi t status` => `git status`
+- `git checkout-b feature` => `git checkout -b feature`
+Subcommands are only fixed for commands whose subcommands we know (see `rules::subcommands`).
+*/
+pub(crate) struct Whitespace;
+impl Rule for Whitespace {
+    default_rule_id!(Whitespace);
+
+    fn matches(&self, command: &Command, session_metadata: &SessionMetadata) -> bool {
+        let Some(command_name) = command.input_parts().first() else {
+            return false;
+        };
+        if command.exit_code.raw() == 127 {
+            return !session_metadata.is_top_level_command(command_name);
+        }
+
+        let subcommands = subcommands(command_name);
+        command.input_parts().get(1).is_some_and(|subcommand| {
+            !subcommands.is_empty() && !subcommands.contains(&subcommand.as_str())
+        })
+    }
+
+    fn generate_command_corrections<'a>(
+        &self,
+        command: &'a Command,
+        session_metadata: &'a SessionMetadata,
+    ) -> Option<Vec<RuleCorrection<'a>>> {
+        let parts = command.input_parts();
+        let fixes = if command.exit_code.raw() == 127 {
+            fix_command_name(parts, session_metadata)
+        } else {
+            fix_subcommand(parts)
+        };
+
+        let corrections = fixes
+            .into_iter()
+            .map(|fix| {
+                fix.parts
+                    .into_iter()
+                    .chain(parts[fix.replaced..].iter().cloned())
+                    .collect::<Vec<_>>()
+                    .into()
+            })
+            .collect::<Vec<_>>();
+        (!corrections.is_empty()).then_some(corrections)
+    }
+}
+
+/// Replaces the first `replaced` parts of the input with `parts`.
+struct Fix {
+    replaced: usize,
+    parts: Vec<String>,
+}
+
+/// Splits the command name into a known command and a subcommand or argument,
+/// or merges it with the next part.
+fn fix_command_name(parts: &[String], session_metadata: &SessionMetadata) -> Vec<Fix> {
+    let command_name = parts[0].as_str();
+    let mut subcommand_splits = vec![];
+    let mut argument_splits = vec![];
+    for (i, _) in command_name.char_indices().skip(1) {
+        let (prefix, rest) = command_name.split_at(i);
+        if !session_metadata.is_top_level_command(prefix) {
+            continue;
+        }
+
+        let fix = Fix {
+            replaced: 1,
+            parts: vec![prefix.to_owned(), rest.to_owned()],
+        };
+        // Splitting off a known subcommand (or flag) is more likely to be right than splitting off some argument.
+        if subcommands(prefix).contains(&rest) || flags(prefix).contains(&rest) {
+            subcommand_splits.push(fix);
+        } else if rest.starts_with(['-', '.', '/', '~']) {
+            argument_splits.push(fix);
+        }
+    }
+
+    let merge = parts
+        .get(1)
+        .map(|next_part| format!("{command_name}{next_part}"))
+        .filter(|merged| session_metadata.is_top_level_command(merged))
+        .map(|merged| Fix {
+            replaced: 2,
+            parts: vec![merged],
+        });
+
+    subcommand_splits
+        .into_iter()
+        .chain(argument_splits)
+        .chain(merge)
+        .collect()
+}
+
+/// Splits a flag off the subcommand (`checkout-b` => `checkout -b`),
+/// or merges it with the next part (`sta tus` => `status`).
+fn fix_subcommand(parts: &[String]) -> Vec<Fix> {
+    let (Some(command_name), Some(subcommand)) = (parts.first(), parts.get(1)) else {
+        return vec![];
+    };
+    let subcommands = subcommands(command_name);
+
+    let splits = subcommand
+        .match_indices('-')
+        .map(|(i, _)| subcommand.split_at(i))
+        .filter(|(prefix, _)| subcommands.contains(prefix))
+        .map(|(prefix, flag)| Fix {
+            replaced: 2,
+            parts: vec![command_name.clone(), prefix.to_owned(), flag.to_owned()],
+        });
+
+    let merge = parts
+        .get(2)
+        .map(|next_part| format!("{subcommand}{next_part}"))
+        .filter(|merged| subcommands.contains(&merged.as_str()))
+        .map(|merged| Fix {
+            replaced: 3,
+            parts: vec![command_name.clone(), merged],
+        });
+
+    splits.chain(merge).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::{basic_corrections, rule_corrections};
+    use crate::{Command, ExitCode, SessionMetadata};
+
+    const EXECUTABLES: &[&str] = &["git", "ls", "docker", "npm"];
+    const BUILTINS: &[&str] = &["cd"];
+
+    fn corrections(input: &str, exit_code: usize) -> Vec<String> {
+        let mut metadata = SessionMetadata::new();
+        metadata.set_executables(EXECUTABLES.iter().copied());
+        metadata.set_builtins(BUILTINS.iter().copied());
+        let command = Command::new(input, "command not found", ExitCode(exit_code));
+        // Other rules (like NoCommand) may also suggest something, so only apply this one.
+        rule_corrections("Whitespace", command, &metadata)
+    }
+
+    #[test]
+    fn test_missing_whitespace_after_command() {
+        assert_eq!(corrections("gitstatus", 127), vec!["git status"]);
+        assert_eq!(corrections("ls-la", 127), vec!["ls -la"]);
+        assert_eq!(corrections("cd..", 127), vec!["cd .."]);
+        assert_eq!(corrections("dockerps -a", 127), vec!["docker ps -a"]);
+        assert_eq!(corrections("npm--version", 127), vec!["npm --version"]);
+        // `foo` isn't a known command.
+        assert_eq!(corrections("foo-la", 127), Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_extra_whitespace_in_command() {
+        assert_eq!(corrections("gi t status", 127), vec!["git status"]);
+    }
+
+    #[test]
+    fn test_subcommand_whitespace() {
+        assert_eq!(
+            corrections("git  checkout-b feature", 1),
+            vec!["git checkout -b feature"]
+        );
+        assert_eq!(corrections("npm inst all", 1), vec!["npm install"]);
+        assert_eq!(
+            basic_corrections("docker run-it ubuntu", "unknown command"),
+            vec!["docker run -it ubuntu"]
+        );
+        assert_eq!(corrections("git status", 1), Vec::<String>::new());
+    }
+}
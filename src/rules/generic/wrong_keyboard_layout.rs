@@ -0,0 +1,124 @@
+use crate::keyboard::ForeignLayout;
+use crate::rules::Rule;
+use crate::{default_rule_id, Command, RuleCorrection, SessionMetadata};
+
+/*
+Fixes commands typed while a non-Latin keyboard layout was active by mistake,
+e.g. `пше ыефегы` => `git status`. Only an unknown command whose transliteration is a
+known command is fixed. Arguments written wholly in the same script are transliterated too,
+but since non-Latin option values are perfectly valid (e.g. a commit message), the command
+with its arguments as typed is suggested as well if it has some.
+*/
+pub(crate) struct WrongKeyboardLayout;
+impl Rule for WrongKeyboardLayout {
+    default_rule_id!(WrongKeyboardLayout);
+
+    fn matches(&self, command: &Command, session_metadata: &SessionMetadata) -> bool {
+        foreign_layout(command, session_metadata).is_some()
+    }
+
+    fn generate_command_corrections<'a>(
+        &self,
+        command: &'a Command,
+        session_metadata: &'a SessionMetadata,
+    ) -> Option<Vec<RuleCorrection<'a>>> {
+        let (layout, program) = foreign_layout(command, session_metadata)?;
+        let args = &command.input_parts()[1..];
+
+        let mut transliterated = vec![program.clone()];
+        transliterated.extend(args.iter().map(|arg| {
+            if layout.is_script_of(arg) {
+                layout.transliterate(arg, session_metadata.keyboard_layout())
+            } else {
+                arg.clone()
+            }
+        }));
+        let mut corrections = vec![RuleCorrection::from(transliterated)];
+
+        // Option values (e.g. `-m 'исправление'`) may well be meant as typed.
+        let has_foreign_value = args
+            .windows(2)
+            .any(|pair| pair[0].starts_with('-') && layout.is_script_of(&pair[1]));
+        if has_foreign_value {
+            let mut as_typed = vec![program];
+            as_typed.extend(args.iter().cloned());
+            corrections.push(as_typed.into());
+        }
+        Some(corrections)
+    }
+}
+
+/// The foreign layout the command (first word) was typed with, and the command typed with the
+/// user's layout, if it's unknown as typed and a known command once transliterated.
+fn foreign_layout(
+    command: &Command,
+    session_metadata: &SessionMetadata,
+) -> Option<(ForeignLayout, String)> {
+    let typed = command.input_parts().first()?;
+    if session_metadata.is_top_level_command(typed) {
+        return None;
+    }
+    let layout = ForeignLayout::detect(typed)?;
+    let transliterated = layout.transliterate(typed, session_metadata.keyboard_layout());
+    session_metadata
+        .is_top_level_command(&transliterated)
+        .then_some((layout, transliterated))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::keyboard::Layout;
+    use crate::test_utils::regular_corrections;
+    use crate::{Command, ExitCode, SessionMetadata};
+
+    fn session_metadata() -> SessionMetadata<'static> {
+        let mut session_metadata = SessionMetadata::new();
+        session_metadata.set_executables(["git", "ls", "yarn", "echo"]);
+        session_metadata
+    }
+
+    fn corrections(input: &str, session_metadata: &SessionMetadata) -> Vec<String> {
+        let command = Command::new(input, "command not found", ExitCode(127));
+        regular_corrections(command, session_metadata)
+    }
+
+    #[test]
+    fn test_wrong_keyboard_layout() {
+        assert_eq!(
+            corrections("пше ыефегы", &session_metadata()),
+            vec!["git status"]
+        );
+        assert_eq!(
+            corrections("пше status", &session_metadata()),
+            vec!["git status"]
+        );
+        assert_eq!(corrections("λσ -la", &session_metadata()), vec!["ls -la"]);
+        assert_eq!(corrections("ךד -la", &session_metadata()), vec!["ls -la"]);
+    }
+
+    #[test]
+    fn test_with_other_layout() {
+        let mut session_metadata = session_metadata();
+        session_metadata.set_keyboard_layout(Layout::Qwertz);
+        assert_eq!(corrections("яфкт", &session_metadata), vec!["yarn"]);
+    }
+
+    #[test]
+    fn test_non_latin_arguments() {
+        // Option values in the same script may have been meant as typed.
+        assert_eq!(
+            corrections("пше commit -m 'исправление'", &session_metadata()),
+            vec!["git commit -m bcghfdktybt", "git commit -m 'исправление'"]
+        );
+        // Not a known command once transliterated.
+        assert_eq!(
+            corrections("привет", &session_metadata()),
+            Vec::<String>::new()
+        );
+        let command = Command::new("echo привет", "some error", ExitCode(1));
+        assert_eq!(
+            regular_corrections(command, &session_metadata()),
+            Vec::<String>::new()
+        );
+    }
+}
@@ -0,0 +1,82 @@
+use crate::rules::git::invocation::{runs_any, GitLocation};
+use crate::rules::util::{correct_path_at_every_level, new_commands_from_suggestions};
+use crate::rules::Rule;
+use crate::{default_rule_id, Command, RuleCorrection, SessionMetadata};
+use lazy_static::lazy_static;
+use regex::Regex;
+
+lazy_static! {
+    static ref RE: Regex =
+        Regex::new("(?i)fatal: pathspec '([^']*)' did not match any files").unwrap();
+}
+
+/// Corrects a misspelled path in `git add`, e.g. `git add scr/main.rs` => `git add src/main.rs`.
+pub(crate) struct GitAddPathspec;
+impl Rule for GitAddPathspec {
+    default_rule_id!(GitAddPathspec);
+
+    fn matches(&self, command: &Command, session_metadata: &SessionMetadata) -> bool {
+        session_metadata.session_type.is_local()
+            && runs_any(command, session_metadata, &["add"])
+            && RE.is_match(command.output)
+    }
+
+    fn generate_command_corrections<'a>(
+        &self,
+        command: &'a Command,
+        session_metadata: &'a SessionMetadata,
+    ) -> Option<Vec<RuleCorrection<'a>>> {
+        let wrong_path = RE
+            .captures(command.output)
+            .and_then(|captures| captures.get(1))
+            .map(|regex_match| regex_match.as_str())?;
+
+        // Paths are relative to where git runs, e.g. `app` for `git -C app add src`.
+        let location = GitLocation::of(command)?;
+        let corrected_path = correct_path_at_every_level(
+            wrong_path,
+            &location.dir,
+            |path| path.exists(),
+            session_metadata.matcher(self.id()),
+        )?;
+        new_commands_from_suggestions([corrected_path], command.input_parts(), wrong_path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::{regular_corrections, with_temp_directories};
+    use crate::{Command, ExitCode, SessionMetadata};
+
+    #[test]
+    fn test_git_add_pathspec() {
+        with_temp_directories(&["src/rules"], |tempdir| {
+            let command = Command::new(
+                "git add -p sr/rules",
+                "fatal: pathspec 'sr/rules' did not match any files",
+                ExitCode(128),
+            )
+            .set_working_dir(tempdir.path().to_str().unwrap());
+            assert_eq!(
+                regular_corrections(command, &SessionMetadata::new()),
+                vec!["git add -p src/rules"]
+            );
+        });
+    }
+
+    #[test]
+    fn test_git_add_pathspec_in_other_directory() {
+        with_temp_directories(&["app/src/rules"], |tempdir| {
+            let command = Command::new(
+                "git -C app add sr/rules",
+                "fatal: pathspec 'sr/rules' did not match any files",
+                ExitCode(128),
+            )
+            .set_working_dir(tempdir.path().to_str().unwrap());
+            assert_eq!(
+                regular_corrections(command, &SessionMetadata::new()),
+                vec!["git -C app add src/rules"]
+            );
+        });
+    }
+}
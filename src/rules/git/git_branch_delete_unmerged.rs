@@ -0,0 +1,56 @@
+use crate::risk::Risk;
+use crate::rules::git::invocation::{runs_any, GitInvocation};
+use crate::rules::Rule;
+use crate::{default_rule_id, Command, RuleCorrection, SessionMetadata};
+
+/// Force deletes a branch that `git branch -d` refused to delete because it isn't merged.
+pub(crate) struct GitBranchDeleteUnmerged;
+impl Rule for GitBranchDeleteUnmerged {
+    default_rule_id!(GitBranchDeleteUnmerged);
+
+    fn matches(&self, command: &Command, session_metadata: &SessionMetadata) -> bool {
+        runs_any(command, session_metadata, &["branch"])
+            && command.lowercase_output().contains("is not fully merged")
+    }
+
+    fn generate_command_corrections<'a>(
+        &self,
+        command: &'a Command,
+        session_metadata: &'a SessionMetadata,
+    ) -> Option<Vec<RuleCorrection<'a>>> {
+        let git = GitInvocation::parse(command, session_metadata)?;
+        let mut args = git.args().to_vec();
+        match args
+            .iter()
+            .position(|part| part == "-d" || part == "--delete")
+        {
+            Some(delete_pos) => args[delete_pos] = "-D".to_owned(),
+            // e.g. an alias like `bd = branch -d`.
+            None => args.insert(0, "-D".to_owned()),
+        }
+        let new_command = git.with_args(args);
+        Some(vec![new_command.into()])
+    }
+
+    /// The branch's unmerged commits are lost.
+    fn risk(&self) -> Risk {
+        Risk::Destructive
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::basic_corrections;
+
+    #[test]
+    fn test_long_option() {
+        assert_eq!(
+            basic_corrections(
+                "git branch --delete feature other",
+                "error: the branch 'feature' is not fully merged.
+If you are sure you want to delete it, run 'git branch -D feature'."
+            ),
+            vec!["git branch -D feature other"]
+        );
+    }
+}
@@ -0,0 +1,81 @@
+use crate::rules::git::invocation::{runs_any, GitInvocation};
+use crate::rules::Rule;
+use crate::{default_rule_id, Command, RuleCorrection, SessionMetadata};
+use lazy_static::lazy_static;
+use regex::Regex;
+
+lazy_static! {
+    static ref RE: Regex = Regex::new("(?i)a branch named '([^']*)' already exists").unwrap();
+}
+
+// Options that create a branch, and the ones that reset it if it already exists.
+const CREATE_OPTIONS: &[(&str, &str)] =
+    &[("-b", "-B"), ("-c", "-C"), ("--create", "--force-create")];
+
+/*
+Fixes creating a branch that already exists, either by switching to it or by resetting it
+(`git branch --force`, `git checkout -B`, `git switch -C`).
+*/
+pub(crate) struct GitBranchExists;
+impl Rule for GitBranchExists {
+    default_rule_id!(GitBranchExists);
+
+    fn matches(&self, command: &Command, session_metadata: &SessionMetadata) -> bool {
+        runs_any(command, session_metadata, &["branch", "checkout", "switch"])
+            && RE.is_match(command.output)
+    }
+
+    fn generate_command_corrections<'a>(
+        &self,
+        command: &'a Command,
+        session_metadata: &'a SessionMetadata,
+    ) -> Option<Vec<RuleCorrection<'a>>> {
+        let git = GitInvocation::parse(command, session_metadata)?;
+        let branch = RE.captures(command.output)?.get(1)?.as_str();
+        // Switch to the branch the way the user tried to create it.
+        let subcommand = match git.subcommand() {
+            "switch" => "switch",
+            _ => "checkout",
+        };
+        let switch = RuleCorrection::from(git.run([subcommand, branch]));
+
+        // `git checkout -b feature` or `git switch -c feature`.
+        let mut args = git.args().to_vec();
+        if let Some((pos, force_create)) = args.iter().enumerate().find_map(|(pos, part)| {
+            let (_, force_create) = CREATE_OPTIONS.iter().find(|(create, _)| part == create)?;
+            Some((pos, *force_create))
+        }) {
+            args[pos] = force_create.to_owned();
+            return Some(vec![switch, git.with_args(args).into()]);
+        }
+
+        // `git branch feature`
+        if git.subcommand() != "branch" {
+            return Some(vec![switch]);
+        }
+        Some(vec![switch, git.with_flag("--force").into()])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::basic_corrections;
+
+    #[test]
+    fn test_checkout_and_switch() {
+        assert_eq!(
+            basic_corrections(
+                "git checkout -b feature",
+                "fatal: a branch named 'feature' already exists"
+            ),
+            vec!["git checkout feature", "git checkout -B feature"]
+        );
+        assert_eq!(
+            basic_corrections(
+                "git switch -c feature origin/feature",
+                "fatal: a branch named 'feature' already exists"
+            ),
+            vec!["git switch feature", "git switch -C feature origin/feature"]
+        );
+    }
+}
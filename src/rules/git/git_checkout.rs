@@ -1,8 +1,11 @@
 /*
 Fixes error for `git checkout branch_that_doesnt_exist` to be `git checkout -b branch_that_doesnt_exist`.
+Branches that only exist on a remote are checked out with `--track`, and misspelled branches are
+corrected (to local or remote branches). Also applies to `git switch`.
 See more here: https://github.com/nvbn/thefuck/blob/5198b34f24ca4bc414a5bf1b0288ee86ea2529a8/thefuck/rules/git_checkout.py
 */
 
+use crate::rules::git::invocation::{runs_any, GitInvocation};
 use crate::rules::util::{get_single_closest_match, new_commands_from_suggestions};
 use crate::rules::Rule;
 use crate::{default_rule_id, Command, RuleCorrection, SessionMetadata};
@@ -13,15 +16,17 @@ use regex::Regex;
 pub(crate) struct GitCheckout;
 
 lazy_static! {
-    static ref RE: Regex =
-        Regex::new("(?i)error: pathspec '([^']*)' did not match any file").unwrap();
+    static ref RE: Regex = Regex::new(
+        "(?i)error: pathspec '([^']*)' did not match any file|fatal: invalid reference: (\\S+)"
+    )
+    .unwrap();
 }
 
 impl Rule for GitCheckout {
     default_rule_id!(GitCheckout);
 
-    fn matches(&self, command: &Command, _session_metadata: &SessionMetadata) -> bool {
-        command.input_parts().iter().any(|part| part == "checkout")
+    fn matches(&self, command: &Command, session_metadata: &SessionMetadata) -> bool {
+        runs_any(command, session_metadata, &["checkout", "switch"])
             && RE.is_match(command.lowercase_output())
     }
 
@@ -30,43 +35,94 @@ impl Rule for GitCheckout {
         command: &'a Command,
         session_metadata: &'a SessionMetadata,
     ) -> Option<Vec<RuleCorrection<'a>>> {
+        let git = GitInvocation::parse(command, session_metadata)?;
         let mut corrections = vec![];
+        let git_state = session_metadata.git_state(command);
 
-        // The first correction is replacing the unfound branch name with
-        // an existing, similar branch name (if any).
         let wrong_git_branch_name = RE
             .captures(command.output)
-            .and_then(|captures| captures.get(1))
+            .and_then(|captures| captures.get(1).or_else(|| captures.get(2)))
             .map(|regex_match| regex_match.as_str())?;
 
-        let branch_names = session_metadata.git_branches.iter().copied().collect_vec();
-        let closest_git_branch = get_single_closest_match(wrong_git_branch_name, branch_names);
+        // If the branch only exists on a remote, create a local branch that tracks it.
+        let remotes = git_state
+            .remotes_with_branch(wrong_git_branch_name)
+            .collect_vec();
+        if !remotes.is_empty() {
+            for remote in &remotes {
+                corrections.push(track(&git, remote, wrong_git_branch_name));
+            }
+            // `git switch` guesses the remote if there's only one.
+            if remotes.len() == 1 {
+                corrections.push(git.run(["switch", wrong_git_branch_name]).into());
+            }
+            return Some(corrections);
+        }
+
+        // Otherwise, replace the unfound branch name with an existing, similar branch
+        // name (if any). Branches that only exist on a remote are tracked.
+        let remote_only_branches = git_state
+            .remote_branches
+            .values()
+            .flatten()
+            .filter(|branch| !git_state.has_local_branch(branch));
+        let branch_names = git_state
+            .local_branches
+            .iter()
+            .chain(remote_only_branches)
+            .map(String::as_str)
+            .unique()
+            .collect_vec();
+        let matcher = session_metadata.matcher(self.id());
+        let closest_git_branch =
+            get_single_closest_match(wrong_git_branch_name, branch_names, matcher);
 
-        if let Some(closest_git_branch) = closest_git_branch {
-            corrections.extend(
+        match closest_git_branch {
+            Some(branch) if git_state.has_local_branch(branch) => corrections.extend(
                 new_commands_from_suggestions(
-                    [closest_git_branch],
+                    [branch.to_owned()],
                     command.input_parts(),
                     wrong_git_branch_name,
                 )
                 .into_iter()
                 .flatten(),
-            );
+            ),
+            Some(branch) => {
+                let remote = git_state.remotes_with_branch(branch).next()?;
+                corrections.push(track(&git, remote, branch));
+            }
+            None => {}
         }
 
-        // The second correction is a suggestion to create the missing branch.
-        let mut new_branch_correction = command.input_parts().to_vec();
-        let checkout_pos = new_branch_correction.iter().position(|p| p == "checkout")?;
-        new_branch_correction.insert(checkout_pos + 1, "-b".to_owned());
-        corrections.push(new_branch_correction.into());
+        // The last correction is a suggestion to create the missing branch.
+        let create_option = if git.subcommand() == "checkout" {
+            "-b"
+        } else {
+            "-c"
+        };
+        corrections.push(git.with_flag(create_option).into());
 
         Some(corrections)
     }
 }
 
+/// `git checkout --track <remote>/<branch>`
+fn track<'a>(git: &GitInvocation, remote: &str, branch: &str) -> RuleCorrection<'a> {
+    git.run([
+        "checkout".to_owned(),
+        "--track".to_owned(),
+        format!("{remote}/{branch}"),
+    ])
+    .into()
+}
+
 #[cfg(test)]
 mod tests {
+    use std::fs;
+
+    use crate::git::GitState;
     use crate::{test_utils::regular_corrections, Command, ExitCode, SessionMetadata};
+    use tempfile::tempdir;
 
     const GIT_BRANCHES: &[&str] = &["master", "main", "develop"];
 
@@ -101,4 +157,100 @@ mod tests {
             vec!["git checkout -b some-new-branch"]
         )
     }
+
+    fn git_state() -> GitState {
+        GitState {
+            local_branches: vec!["main".to_owned()],
+            remote_branches: [
+                (
+                    "origin".to_owned(),
+                    vec!["main".to_owned(), "feature".to_owned()],
+                ),
+                (
+                    "fork".to_owned(),
+                    vec!["experiment".to_owned(), "feature".to_owned()],
+                ),
+            ]
+            .into_iter()
+            .collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_git_checkout_remote_branch() {
+        let mut session_metadata = SessionMetadata::new();
+        session_metadata.set_git_state(git_state());
+
+        let command = Command::new(
+            "git checkout experiment",
+            "error: pathspec 'experiment' did not match any file(s) known to git",
+            ExitCode(1),
+        );
+        assert_eq!(
+            regular_corrections(command, &session_metadata),
+            vec![
+                "git checkout --track fork/experiment",
+                "git switch experiment"
+            ]
+        );
+
+        // More than one remote has the branch.
+        let command = Command::new(
+            "git checkout feature",
+            "error: pathspec 'feature' did not match any file(s) known to git",
+            ExitCode(1),
+        );
+        assert_eq!(
+            regular_corrections(command, &session_metadata),
+            vec![
+                "git checkout --track fork/feature",
+                "git checkout --track origin/feature"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_git_switch_misspelled_remote_branch() {
+        let mut session_metadata = SessionMetadata::new();
+        session_metadata.set_git_state(git_state());
+        let command = Command::new(
+            "git switch experimnt",
+            "fatal: invalid reference: experimnt",
+            ExitCode(128),
+        );
+        assert_eq!(
+            regular_corrections(command, &session_metadata),
+            vec![
+                "git checkout --track fork/experiment",
+                "git switch -c experimnt"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_git_checkout_in_other_directory() {
+        // The branches are read from the repository `-C` points to.
+        let root = tempdir().unwrap();
+        let git_dir = root.path().join("other/.git");
+        fs::create_dir_all(git_dir.join("refs/heads")).unwrap();
+        fs::write(git_dir.join("HEAD"), "ref: refs/heads/main\n").unwrap();
+        for branch in ["main", "feature"] {
+            fs::write(git_dir.join("refs/heads").join(branch), "0123456789abcdef").unwrap();
+        }
+
+        let command = Command::new(
+            "git -C other checkout featur",
+            "error: pathspec 'featur' did not match any file(s) known to git",
+            ExitCode(1),
+        )
+        .set_working_dir(root.path().to_str().unwrap());
+        assert_eq!(
+            regular_corrections(command, &SessionMetadata::new()),
+            vec![
+                "git -C other checkout feature",
+                "git -C other checkout -b featur"
+            ]
+        );
+    }
 }
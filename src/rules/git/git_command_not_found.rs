@@ -134,7 +134,7 @@ mod tests {
                     commit
                 "
             ),
-            vec![r#"git commit -m "my fancy message""#]
+            vec!["git commit -m 'my fancy message'"]
         )
     }
 }
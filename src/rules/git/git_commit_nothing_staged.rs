@@ -0,0 +1,59 @@
+use crate::rules::git::invocation::{runs_any, GitInvocation};
+use crate::rules::Rule;
+use crate::{default_rule_id, Command, RuleCorrection, SessionMetadata};
+
+const NO_CHANGES_ADDED: &str = "no changes added to commit";
+const ONLY_UNTRACKED: &str = "nothing added to commit but untracked files present";
+
+/// Fixes a `git commit` with nothing staged, by committing all changes (`git commit -a`)
+/// or by adding everything first (`git add -A`), which also adds untracked files.
+pub(crate) struct GitCommitNothingStaged;
+impl Rule for GitCommitNothingStaged {
+    default_rule_id!(GitCommitNothingStaged);
+
+    fn matches(&self, command: &Command, session_metadata: &SessionMetadata) -> bool {
+        let lowercase_output = command.lowercase_output();
+        runs_any(command, session_metadata, &["commit"])
+            && (lowercase_output.contains(NO_CHANGES_ADDED)
+                || lowercase_output.contains(ONLY_UNTRACKED))
+    }
+
+    fn generate_command_corrections<'a>(
+        &self,
+        command: &'a Command,
+        session_metadata: &'a SessionMetadata,
+    ) -> Option<Vec<RuleCorrection<'a>>> {
+        let git = GitInvocation::parse(command, session_metadata)?;
+        let mut corrections = vec![];
+        // `-a` only commits files git already tracks.
+        if command.lowercase_output().contains(NO_CHANGES_ADDED) {
+            corrections.push(git.with_flag("-a").into());
+        }
+        corrections.push(RuleCorrection::and(
+            git.run(["add", "-A"]),
+            command.input_parts(),
+        ));
+        Some(corrections)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::basic_corrections;
+
+    #[test]
+    fn test_only_untracked() {
+        assert_eq!(
+            basic_corrections(
+                "git commit -m 'add notes'",
+                r#"On branch main
+Untracked files:
+  (use "git add <file>..." to include in what will be committed)
+	notes.txt
+
+nothing added to commit but untracked files present (use "git add" to track)"#
+            ),
+            vec!["git add -A && git commit -m 'add notes'"]
+        );
+    }
+}
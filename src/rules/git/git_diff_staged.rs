@@ -0,0 +1,59 @@
+use crate::rules::git::invocation::{runs_any, GitInvocation};
+use crate::rules::Rule;
+use crate::{default_rule_id, Command, RuleCorrection, SessionMetadata};
+
+/*
+Suggests `git diff --staged` when `git diff` shows nothing, since all changes may be staged.
+`git diff` succeeds in that case, so this rule is considered even if the command didn't fail.
+*/
+pub(crate) struct GitDiffStaged;
+impl Rule for GitDiffStaged {
+    default_rule_id!(GitDiffStaged);
+
+    fn should_be_considered_by_default(
+        &self,
+        command: &Command,
+        session_metadata: &SessionMetadata,
+    ) -> bool {
+        command.output.trim().is_empty() && runs_any(command, session_metadata, &["diff"])
+    }
+
+    fn matches(&self, command: &Command, session_metadata: &SessionMetadata) -> bool {
+        // Already comparing something else than the working tree to the index (e.g. `HEAD`).
+        GitInvocation::parse(command, session_metadata).is_some_and(|git| {
+            !git.expanded_args().any(|part| {
+                ["--staged", "--cached", "--no-index"].contains(&part) || !part.starts_with('-')
+            })
+        })
+    }
+
+    fn generate_command_corrections<'a>(
+        &self,
+        command: &'a Command,
+        session_metadata: &'a SessionMetadata,
+    ) -> Option<Vec<RuleCorrection<'a>>> {
+        let git = GitInvocation::parse(command, session_metadata)?;
+        Some(vec![git.with_flag("--staged").into()])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::{basic_corrections, regular_corrections};
+    use crate::{Command, ExitCode, SessionMetadata};
+
+    #[test]
+    fn test_git_diff_staged() {
+        let command = Command::new("git diff --stat", "", ExitCode(0));
+        assert_eq!(
+            regular_corrections(command, &SessionMetadata::new()),
+            vec!["git diff --staged --stat"]
+        );
+
+        // There's a diff, or the user is diffing something else.
+        let command = Command::new("git diff", "diff --git a/a b/a", ExitCode(0));
+        assert!(regular_corrections(command, &SessionMetadata::new()).is_empty());
+        assert!(basic_corrections("git diff main", "").is_empty());
+        assert!(basic_corrections("git diff --cached", "").is_empty());
+    }
+}
@@ -0,0 +1,55 @@
+use crate::rules::git::invocation::GitInvocation;
+use crate::rules::Rule;
+use crate::{default_rule_id, Command, RuleCorrection, SessionMetadata};
+use lazy_static::lazy_static;
+use regex::Regex;
+
+lazy_static! {
+    // e.g. `git-lfs filter-process: git-lfs: command not found` (from the LFS filter in
+    // `.gitattributes`), or `This repository is configured for Git LFS but 'git-lfs' was not
+    // found on your path.` (from its hooks).
+    static ref RE: Regex = Regex::new(
+        r"(?i)git-lfs(?: filter-process)?: (?:git-lfs: )?command not found|configured for git lfs but 'git-lfs' was not found"
+    )
+    .unwrap();
+}
+
+/// Sets up Git LFS (`git lfs install`) when a repository uses it but git can't run it, e.g.
+/// `git pull` => `git lfs install && git pull`.
+pub(crate) struct GitLfsInstall;
+impl Rule for GitLfsInstall {
+    default_rule_id!(GitLfsInstall);
+
+    fn matches(&self, command: &Command, _session_metadata: &SessionMetadata) -> bool {
+        RE.is_match(command.output)
+    }
+
+    fn generate_command_corrections<'a>(
+        &self,
+        command: &'a Command,
+        session_metadata: &'a SessionMetadata,
+    ) -> Option<Vec<RuleCorrection<'a>>> {
+        let git = GitInvocation::parse(command, session_metadata)?;
+        Some(vec![RuleCorrection::and(
+            git.run(["lfs", "install"]),
+            command.input_parts(),
+        )])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::basic_corrections;
+
+    #[test]
+    fn test_filter_process() {
+        assert_eq!(
+            basic_corrections(
+                "git checkout release",
+                "git-lfs filter-process: git-lfs: command not found
+fatal: the remote end hung up unexpectedly"
+            ),
+            vec!["git lfs install && git checkout release"]
+        );
+    }
+}
@@ -0,0 +1,78 @@
+use crate::rules::git::invocation::GitInvocation;
+use crate::rules::Rule;
+use crate::{default_rule_id, Command, RuleCorrection, SessionMetadata};
+use itertools::Itertools;
+use lazy_static::lazy_static;
+use regex::Regex;
+
+lazy_static! {
+    // e.g. `Encountered 2 file(s) that should have been pointers, but weren't:`, followed by
+    // the files (indented).
+    static ref RE: Regex = Regex::new(
+        r"(?i)encountered \d+ file(?:\(s\)|s)? that (?:should|may not) have been (?:pointers|replaced with pointers)[^\n]*\n((?:[ \t]+\S.*(?:\n|$))+)"
+    )
+    .unwrap();
+}
+
+/*
+Converts files that are tracked by Git LFS (in `.gitattributes`) but were committed as regular
+files, e.g. `git push` => `git lfs migrate import --no-rewrite design.psd && git push`.
+`--no-rewrite` adds a commit instead of rewriting history.
+*/
+pub(crate) struct GitLfsMigrate;
+impl Rule for GitLfsMigrate {
+    default_rule_id!(GitLfsMigrate);
+
+    fn matches(&self, command: &Command, _session_metadata: &SessionMetadata) -> bool {
+        RE.is_match(command.output)
+    }
+
+    fn generate_command_corrections<'a>(
+        &self,
+        command: &'a Command,
+        session_metadata: &'a SessionMetadata,
+    ) -> Option<Vec<RuleCorrection<'a>>> {
+        let git = GitInvocation::parse(command, session_metadata)?;
+        let files = RE
+            .captures(command.output)?
+            .get(1)?
+            .as_str()
+            .lines()
+            .map(str::trim)
+            .filter(|file| !file.is_empty())
+            .map(str::to_owned)
+            .collect_vec();
+
+        let migrate = git.run(
+            ["lfs", "migrate", "import", "--no-rewrite"]
+                .map(str::to_owned)
+                .into_iter()
+                .chain(files),
+        );
+        // Nothing to retry if the user was just checking.
+        if git.is_any(&["status", "lfs"]) {
+            return Some(vec![migrate.into()]);
+        }
+        Some(vec![RuleCorrection::and(migrate, command.input_parts())])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::basic_corrections;
+
+    #[test]
+    fn test_pointers() {
+        assert_eq!(
+            basic_corrections(
+                "git push",
+                "Encountered 2 file(s) that should have been pointers, but weren't:
+\tassets/logo.psd
+\tassets/intro video.mp4
+To github.com:org/repo.git
+ ! [rejected]        main -> main (pre-receive hook declined)"
+            ),
+            vec!["git lfs migrate import --no-rewrite assets/logo.psd 'assets/intro video.mp4' && git push"]
+        );
+    }
+}
@@ -0,0 +1,93 @@
+use crate::rules::git::invocation::runs_any;
+use crate::rules::util::{get_single_closest_match, new_commands_from_suggestions};
+use crate::rules::Rule;
+use crate::{default_rule_id, Command, RuleCorrection, SessionMetadata};
+use itertools::Itertools;
+use lazy_static::lazy_static;
+use regex::Regex;
+
+lazy_static! {
+    static ref RE: Regex =
+        Regex::new("(?i)merge: (\\S+) - not something we can merge").unwrap();
+    // git lists similar refs after `Did you mean this?` (or `these?`), one per (indented) line.
+    static ref SUGGESTIONS_RE: Regex =
+        Regex::new("(?i)Did you mean (?:this|these)\\?\n((?:[ \t]+\\S+\n?)+)").unwrap();
+}
+
+/// Corrects a misspelled branch in `git merge`, using git's suggestions or else the
+/// closest known (local or remote-tracking) branch.
+pub(crate) struct GitMergeUnknownBranch;
+impl Rule for GitMergeUnknownBranch {
+    default_rule_id!(GitMergeUnknownBranch);
+
+    fn matches(&self, command: &Command, session_metadata: &SessionMetadata) -> bool {
+        runs_any(command, session_metadata, &["merge"]) && RE.is_match(command.output)
+    }
+
+    fn generate_command_corrections<'a>(
+        &self,
+        command: &'a Command,
+        session_metadata: &'a SessionMetadata,
+    ) -> Option<Vec<RuleCorrection<'a>>> {
+        let unknown = RE.captures(command.output)?.get(1)?.as_str();
+
+        if let Some(suggestions) = SUGGESTIONS_RE
+            .captures(command.output)
+            .and_then(|captures| captures.get(1))
+        {
+            let suggestions = suggestions.as_str().split_whitespace().collect_vec();
+            return new_commands_from_suggestions(suggestions, command.input_parts(), unknown);
+        }
+
+        let git_state = session_metadata.git_state(command);
+        let branch_names = git_state
+            .local_branches
+            .iter()
+            .cloned()
+            .chain(git_state.qualified_remote_branches())
+            .collect_vec();
+        let matcher = session_metadata.matcher(self.id());
+        let branch =
+            get_single_closest_match(unknown, branch_names.iter().map(String::as_str), matcher)?;
+        new_commands_from_suggestions([branch.to_owned()], command.input_parts(), unknown)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::{basic_corrections, regular_corrections};
+    use crate::{Command, ExitCode, SessionMetadata};
+
+    #[test]
+    fn test_git_suggestions() {
+        assert_eq!(
+            basic_corrections(
+                "git merge --no-ff featre",
+                "merge: featre - not something we can merge
+
+Did you mean these?
+	feature
+	origin/feature"
+            ),
+            vec![
+                "git merge --no-ff feature",
+                "git merge --no-ff origin/feature"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_known_branches() {
+        let mut session_metadata = SessionMetadata::new();
+        session_metadata.set_git_branches(["main", "develop"]);
+        let command = Command::new(
+            "git merge devlop",
+            "merge: devlop - not something we can merge",
+            ExitCode(1),
+        );
+        assert_eq!(
+            regular_corrections(command, &session_metadata),
+            vec!["git merge develop"]
+        );
+    }
+}
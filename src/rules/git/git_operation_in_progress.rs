@@ -0,0 +1,209 @@
+use crate::git::GitOperation;
+use crate::rules::git::invocation::GitInvocation;
+use crate::rules::Rule;
+use crate::{default_rule_id, Command, RuleCorrection, SessionMetadata};
+use itertools::Itertools;
+use lazy_static::lazy_static;
+use regex::Regex;
+
+lazy_static! {
+    // What git says when an operation is in progress, e.g. `fatal: You have not concluded your
+    // merge (MERGE_HEAD exists).` for `git merge`, or `fatal: It seems that there is already a
+    // rebase-merge directory` for `git rebase`.
+    static ref MERGE_RE: Regex = Regex::new(
+        r"(?i)not concluded your merge|you are (?:currently|in the middle of) (?:a )?merg"
+    )
+    .unwrap();
+    static ref REBASE_RE: Regex = Regex::new(
+        r"(?i)already a rebase-merge directory|you are currently (?:editing a commit )?(?:while )?rebasing|interactive rebase in progress|rebase is already in progress"
+    )
+    .unwrap();
+    static ref CHERRY_PICK_RE: Regex = Regex::new(
+        r"(?i)cherry-pick is already in progress|not concluded your cherry-pick|you are currently cherry-picking"
+    )
+    .unwrap();
+    static ref REVERT_RE: Regex = Regex::new(
+        r"(?i)revert is already in progress|you are currently reverting"
+    )
+    .unwrap();
+    static ref AM_RE: Regex =
+        Regex::new(r"(?i)'git am' is in progress|in the middle of an am session").unwrap();
+    static ref BISECT_RE: Regex = Regex::new(r"(?i)you are currently bisecting").unwrap();
+
+    // What git says when conflicts get in the way of a later command, without saying which
+    // operation they're from, e.g. `error: Committing is not possible because you have unmerged
+    // files.` The conflicts reported by the operation itself (`fix conflicts and then commit the
+    // result`) still have to be resolved, so there's nothing to suggest yet.
+    static ref UNMERGED_RE: Regex = Regex::new(
+        r"(?i)you have unmerged files|: needs merge|you must edit all merge conflicts"
+    )
+    .unwrap();
+
+    // The files to add when continuing, e.g. `src/main.rs: needs merge` after
+    // `git rebase --continue`, which is how git asks to mark them as resolved.
+    static ref CONFLICTED_FILE_RE: Regex = Regex::new(r"(?m)^(.+): needs merge$").unwrap();
+}
+
+/*
+Suggests how to get out of an operation that's in progress (and in the way of the command),
+e.g. `git rebase --continue`, `--abort` or `--skip`. The operation is recognized from the output,
+or else from the repository. If continuing failed because files weren't marked as resolved,
+they're added before continuing: `git add src/main.rs && git rebase --continue`.
+*/
+pub(crate) struct GitOperationInProgress;
+impl Rule for GitOperationInProgress {
+    default_rule_id!(GitOperationInProgress);
+
+    fn matches(&self, command: &Command, session_metadata: &SessionMetadata) -> bool {
+        operation(command, session_metadata).is_some()
+    }
+
+    fn generate_command_corrections<'a>(
+        &self,
+        command: &'a Command,
+        session_metadata: &'a SessionMetadata,
+    ) -> Option<Vec<RuleCorrection<'a>>> {
+        let git = GitInvocation::parse(command, session_metadata)?;
+        let subcommand = match operation(command, session_metadata)? {
+            GitOperation::Bisect => return Some(vec![git.run(["bisect", "reset"]).into()]),
+            GitOperation::Merge => "merge",
+            GitOperation::Rebase => "rebase",
+            GitOperation::CherryPick => "cherry-pick",
+            GitOperation::Revert => "revert",
+            GitOperation::Am => "am",
+        };
+
+        let conflicted_files = CONFLICTED_FILE_RE
+            .captures_iter(command.output)
+            .filter_map(|captures| captures.get(1))
+            .map(|file| file.as_str().trim().to_owned())
+            .unique()
+            .collect_vec();
+
+        let resume = git.run([subcommand, "--continue"]);
+        let mut corrections = vec![if conflicted_files.is_empty() {
+            resume.into()
+        } else {
+            let add = git.run(["add".to_owned()].into_iter().chain(conflicted_files));
+            RuleCorrection::and(add, resume)
+        }];
+        corrections.push(git.run([subcommand, "--abort"]).into());
+        // A merge is a single step, so there's nothing to skip.
+        if subcommand != "merge" {
+            corrections.push(git.run([subcommand, "--skip"]).into());
+        }
+        Some(corrections)
+    }
+}
+
+/// The operation that's in the way of the command, if any.
+fn operation(command: &Command, session_metadata: &SessionMetadata) -> Option<GitOperation> {
+    let from_output = [
+        (&*MERGE_RE, GitOperation::Merge),
+        (&*REBASE_RE, GitOperation::Rebase),
+        (&*CHERRY_PICK_RE, GitOperation::CherryPick),
+        (&*REVERT_RE, GitOperation::Revert),
+        (&*AM_RE, GitOperation::Am),
+        (&*BISECT_RE, GitOperation::Bisect),
+    ]
+    .into_iter()
+    .find(|(re, _)| re.is_match(command.output))
+    .map(|(_, operation)| operation);
+
+    from_output.or_else(|| {
+        if UNMERGED_RE.is_match(command.output) {
+            session_metadata.git_state(command).operation
+        } else {
+            None
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::git::{GitOperation, GitState};
+    use crate::test_utils::{basic_corrections, regular_corrections};
+    use crate::{Command, ExitCode, SessionMetadata};
+
+    #[test]
+    fn test_from_output() {
+        assert_eq!(
+            basic_corrections(
+                "git merge feature",
+                "error: You have not concluded your merge (MERGE_HEAD exists).
+hint: Please, commit your changes before merging.
+fatal: Exiting because of unfinished merge."
+            ),
+            vec!["git merge --continue", "git merge --abort"]
+        );
+        assert_eq!(
+            basic_corrections(
+                "git checkout main",
+                "You are currently bisecting, started from branch 'main'."
+            ),
+            vec!["git bisect reset"]
+        );
+    }
+
+    #[test]
+    fn test_conflicted_files_from_repository() {
+        let mut session_metadata = SessionMetadata::new();
+        session_metadata.set_git_state(GitState {
+            operation: Some(GitOperation::CherryPick),
+            ..Default::default()
+        });
+        let command = Command::new(
+            "git cherry-pick --continue",
+            "src/main.rs: needs merge\nREADME with spaces.md: needs merge\nYou must edit all merge conflicts and then\nmark them as resolved using git add",
+            ExitCode(128),
+        );
+        assert_eq!(
+            regular_corrections(command, &session_metadata),
+            vec![
+                "git add src/main.rs 'README with spaces.md' && git cherry-pick --continue",
+                "git cherry-pick --abort",
+                "git cherry-pick --skip",
+            ]
+        );
+
+        // Git doesn't say the files are resolved, so they aren't added.
+        let command = Command::new(
+            "git commit",
+            "U\tsrc/main.rs\nerror: Committing is not possible because you have unmerged files.",
+            ExitCode(128),
+        );
+        assert_eq!(
+            regular_corrections(command, &session_metadata),
+            vec![
+                "git cherry-pick --continue",
+                "git cherry-pick --abort",
+                "git cherry-pick --skip",
+            ]
+        );
+
+        // Without an operation in progress, there's nothing to continue.
+        let command = Command::new(
+            "git commit",
+            "error: Committing is not possible because you have unmerged files.",
+            ExitCode(128),
+        );
+        assert!(regular_corrections(command, &SessionMetadata::new()).is_empty());
+    }
+
+    #[test]
+    fn test_conflicts_from_the_operation() {
+        let mut session_metadata = SessionMetadata::new();
+        session_metadata.set_git_state(GitState {
+            operation: Some(GitOperation::Merge),
+            ..Default::default()
+        });
+        let command = Command::new(
+            "git merge feature",
+            "Auto-merging src/main.rs
+CONFLICT (content): Merge conflict in src/main.rs
+Automatic merge failed; fix conflicts and then commit the result.",
+            ExitCode(1),
+        );
+        assert!(regular_corrections(command, &session_metadata).is_empty());
+    }
+}
@@ -0,0 +1,54 @@
+use crate::rules::git::invocation::{runs_any, GitInvocation};
+use crate::rules::Rule;
+use crate::{default_rule_id, Command, RuleCorrection, SessionMetadata};
+
+// The ways git offers to reconcile divergent branches.
+const RECONCILE_OPTIONS: &[&str] = &["--rebase", "--no-rebase", "--ff-only"];
+
+/// Fixes a `git pull` that doesn't know how to reconcile divergent branches (since git 2.27,
+/// when `pull.rebase` isn't configured), by passing one of the options git suggests.
+pub(crate) struct GitPullDivergent;
+impl Rule for GitPullDivergent {
+    default_rule_id!(GitPullDivergent);
+
+    fn matches(&self, command: &Command, session_metadata: &SessionMetadata) -> bool {
+        runs_any(command, session_metadata, &["pull"])
+            && command
+                .lowercase_output()
+                .contains("need to specify how to reconcile divergent branches")
+    }
+
+    fn generate_command_corrections<'a>(
+        &self,
+        command: &'a Command,
+        session_metadata: &'a SessionMetadata,
+    ) -> Option<Vec<RuleCorrection<'a>>> {
+        let git = GitInvocation::parse(command, session_metadata)?;
+        Some(
+            RECONCILE_OPTIONS
+                .iter()
+                .map(|option| git.with_flag(option).into())
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::basic_corrections;
+
+    #[test]
+    fn test_with_remote() {
+        assert_eq!(
+            basic_corrections(
+                "git pull origin main",
+                "fatal: Need to specify how to reconcile divergent branches."
+            ),
+            vec![
+                "git pull --rebase origin main",
+                "git pull --no-rebase origin main",
+                "git pull --ff-only origin main"
+            ]
+        );
+    }
+}
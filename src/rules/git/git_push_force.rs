@@ -1,3 +1,6 @@
+use crate::risk::Risk;
+use crate::rules::git::invocation::{runs_any, GitInvocation};
+use crate::rules::util::with_flag_after;
 use crate::rules::Rule;
 use crate::{default_rule_id, Command, RuleCorrection, SessionMetadata};
 
@@ -9,9 +12,9 @@ pub(crate) struct GitPushForce;
 impl Rule for GitPushForce {
     default_rule_id!(GitPushForce);
 
-    fn matches(&self, command: &Command, _session_metadata: &SessionMetadata) -> bool {
+    fn matches(&self, command: &Command, session_metadata: &SessionMetadata) -> bool {
         let lowercase_output = command.lowercase_output();
-        command.input_parts().iter().any(|part| part == "push")
+        runs_any(command, session_metadata, &["push"])
             && lowercase_output.contains("! [rejected]")
             && lowercase_output.contains("failed to push some refs")
             && lowercase_output
@@ -21,18 +24,42 @@ impl Rule for GitPushForce {
     fn generate_command_corrections<'a>(
         &self,
         command: &'a Command,
-        _session_metadata: &'a SessionMetadata,
+        session_metadata: &'a SessionMetadata,
     ) -> Option<Vec<RuleCorrection<'a>>> {
-        let mut new_command = command.input_parts().to_vec();
-        let push_index = new_command.iter().position(|part| part == "push")?;
-        new_command.insert(push_index + 1, "--force-with-lease".to_owned());
-        Some(vec![new_command.into()])
+        let new_command =
+            GitInvocation::parse(command, session_metadata)?.with_flag("--force-with-lease");
+        let preview = with_flag_after(&new_command, "--force-with-lease", "--dry-run")?;
+        Some(vec![RuleCorrection::from(new_command).with_preview(preview)])
+    }
+
+    /// Overwrites the remote branch.
+    fn risk(&self) -> Risk {
+        Risk::Destructive
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::test_utils::basic_corrections;
+    use crate::test_utils::{basic_corrections, basic_previews};
+
+    const OUTPUT: &str = "To github.com:org/repo.git
+                  ! [rejected]        branch/name -> branch/name (non-fast-forward)
+                 error: failed to push some refs to 'github.com:org/repo.git'
+                 hint: Updates were rejected because the tip of your current branch is behind
+                 hint: its remote counterpart. Integrate the remote changes (e.g.
+                 hint: 'git pull ...') before pushing again.
+                 hint: See the 'Note about fast-forwards' in 'git push --help' for details.
+                ";
+
+    #[test]
+    fn test_git_push_force_preview() {
+        assert_eq!(
+            basic_previews("git push some-other-arg", OUTPUT),
+            vec![Some(
+                "git push --force-with-lease --dry-run some-other-arg".to_owned()
+            )]
+        )
+    }
 
     #[test]
     fn test_git_push_force() {
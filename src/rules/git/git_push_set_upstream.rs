@@ -1,5 +1,8 @@
+use crate::rules::git::invocation::{runs_any, GitInvocation};
 use crate::rules::Rule;
-use crate::{default_rule_id, Command, RuleCorrection, SessionMetadata};
+use crate::{
+    default_rule_id, Command, PlaceholderKind, RuleCorrection, SessionMetadata, TemplatePart,
+};
 use lazy_static::lazy_static;
 use regex::Regex;
 
@@ -11,38 +14,50 @@ lazy_static! {
         Regex::new(format!("git push {SET_UPSTREAM_LONG_NAME} (.*) (.*)").as_str()).unwrap();
 }
 
+const NO_REMOTE: &str = "no configured push destination";
+
 /*
 Fixes a git push command that is missing the "--set-upstream" option and arg.
+If the repository doesn't have a remote yet, suggests adding one first. Its URL is a placeholder,
+and so is its name (`origin` by default), in both the `git remote add` and the `git push`.
 See more here: https://github.com/nvbn/thefuck/blob/5198b34f24ca4bc414a5bf1b0288ee86ea2529a8/thefuck/rules/git_push.py
 */
 pub(crate) struct GitPushSetUpstream;
 impl Rule for GitPushSetUpstream {
     default_rule_id!(GitPushSetUpstream);
 
-    fn matches(&self, command: &Command, _session_metadata: &SessionMetadata) -> bool {
-        command.input_parts().iter().any(|part| part == "push") && RE.is_match(command.output)
+    fn matches(&self, command: &Command, session_metadata: &SessionMetadata) -> bool {
+        runs_any(command, session_metadata, &["push"])
+            && (RE.is_match(command.output) || command.lowercase_output().contains(NO_REMOTE))
     }
 
     fn generate_command_corrections<'a>(
         &self,
         command: &'a Command,
-        _session_metadata: &'a SessionMetadata,
+        session_metadata: &'a SessionMetadata,
     ) -> Option<Vec<RuleCorrection<'a>>> {
-        let command_parts = command.input_parts();
-        let mut new_command_parts = vec!["git", "push", SET_UPSTREAM_LONG_NAME];
+        let git = GitInvocation::parse(command, session_metadata)?;
 
-        // Get the suggested remote and branch names
-        let (remote, branch) = RE
+        // Get the suggested remote and branch names. Without a remote, we add `origin` and
+        // push the current branch (`HEAD` if we don't know it).
+        let suggestion = RE
             .captures(command.output)
             .and_then(|captures| Some((captures.get(1)?, captures.get(2)?)))
-            .map(|(remote, branch)| (remote.as_str(), branch.as_str()))?;
+            .map(|(remote, branch)| (remote.as_str().to_owned(), branch.as_str().to_owned()));
+        let (remote, branch) = suggestion.clone().unwrap_or_else(|| {
+            let current_branch = session_metadata.git_state(command).current_branch.clone();
+            (
+                "origin".to_owned(),
+                current_branch.unwrap_or_else(|| "HEAD".to_owned()),
+            )
+        });
 
         // Add the suggested git remote to the corrected comamnd
-        new_command_parts.push(remote);
-        new_command_parts.push(branch);
+        let mut args = vec![SET_UPSTREAM_LONG_NAME.to_owned(), remote, branch];
 
         // Add any options except --set-upstream and -u back to the command
         // because the suggested git command wouldn't have included them
+        let command_parts = git.args();
         let mut idx = 0;
         while idx < command_parts.len() {
             let part = &command_parts[idx];
@@ -51,19 +66,60 @@ impl Rule for GitPushSetUpstream {
                     // --set-upstream also has an arg so skip that as well
                     idx += 1;
                 } else if part != SET_UPSTREAM_SHORT_NAME {
-                    new_command_parts.push(part);
+                    args.push(part.clone());
                 }
             }
             idx += 1;
         }
 
-        Some(vec![new_command_parts.into()])
+        if suggestion.is_some() {
+            let preview = git.with_args(["--dry-run".to_owned()].into_iter().chain(args.clone()));
+            return Some(vec![
+                RuleCorrection::from(git.with_args(args)).with_preview(preview)
+            ]);
+        }
+        let args_len = args.len();
+        let add_remote = RuleCorrection::Template(
+            git.run(["remote", "add"])
+                .into_iter()
+                .map(TemplatePart::from)
+                .chain([
+                    remote_placeholder(),
+                    TemplatePart::placeholder("url", PlaceholderKind::Url),
+                ])
+                .collect(),
+        );
+        // The push uses the same remote placeholder, so editing one edits both.
+        let push = git.with_args(args);
+        let remote_index = push.len() - args_len + 1;
+        let push = RuleCorrection::Template(
+            push.into_iter()
+                .enumerate()
+                .map(|(i, part)| {
+                    if i == remote_index {
+                        remote_placeholder()
+                    } else {
+                        part.into()
+                    }
+                })
+                .collect(),
+        );
+        Some(vec![RuleCorrection::and(add_remote, push)])
     }
 }
 
+/// The remote to add when there's none, which the user may want to name differently.
+fn remote_placeholder<'a>() -> TemplatePart<'a> {
+    TemplatePart::placeholder_with_default("remote", PlaceholderKind::Remote, "origin")
+}
+
 #[cfg(test)]
 mod tests {
+    use crate::risk::Risk;
     use crate::test_utils::basic_corrections;
+    use crate::{
+        correct_command, Command, ExitCode, Placeholder, PlaceholderKind, SessionMetadata,
+    };
 
     #[test]
     fn test_git_push_incorrect_command() {
@@ -97,6 +153,25 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_git_push_no_remote() {
+        assert_eq!(
+            basic_corrections(
+                "git push --tags",
+                "fatal: No configured push destination.
+Either specify the URL from the command-line or configure a remote repository using
+
+    git remote add <name> <url>
+
+and then push using the remote name
+
+    git push <name>
+"
+            ),
+            vec!["git remote add origin <url> && git push --set-upstream origin HEAD --tags"]
+        )
+    }
+
     #[test]
     fn test_git_push_incorrect_command_with_options() {
         assert_eq!(
@@ -111,4 +186,57 @@ mod tests {
             vec!["git push --set-upstream origin random --force-with-lease"]
         )
     }
+
+    #[test]
+    fn test_alias_and_global_options() {
+        let mut session_metadata = SessionMetadata::new();
+        session_metadata.set_git_aliases([("p", "push")]);
+        let command = Command::new(
+            "git -C app p --tags",
+            "fatal: The current branch random has no upstream branch.
+To push the current branch and set the remote as upstream, use
+
+    git push --set-upstream origin random",
+            ExitCode(128),
+        );
+        let corrections = correct_command(command, &session_metadata);
+        assert_eq!(
+            corrections[0].command,
+            "git -C app p --set-upstream origin random --tags"
+        );
+        assert_eq!(corrections[0].risk, Risk::NetworkPush);
+    }
+
+    #[test]
+    fn test_git_push_no_remote_placeholders() {
+        let command = Command::new(
+            "git push",
+            "fatal: No configured push destination.",
+            ExitCode(128),
+        );
+        let corrections = correct_command(command, &SessionMetadata::new());
+        assert_eq!(
+            corrections[0].command,
+            "git remote add origin <url> && git push --set-upstream origin HEAD"
+        );
+        let remote = |span| Placeholder {
+            name: "remote".to_owned(),
+            kind: PlaceholderKind::Remote,
+            default: Some("origin".to_owned()),
+            span,
+        };
+        assert_eq!(
+            corrections[0].placeholders,
+            vec![
+                remote(15..21),
+                Placeholder {
+                    name: "url".to_owned(),
+                    kind: PlaceholderKind::Url,
+                    default: None,
+                    span: 22..27
+                },
+                remote(55..61)
+            ]
+        );
+    }
 }
@@ -0,0 +1,136 @@
+use crate::rules::git::invocation::{runs_any, GitInvocation};
+use crate::rules::git::remote_url::{remote_of, RemoteUrl};
+use crate::rules::util::new_commands_from_suggestions;
+use crate::rules::Rule;
+use crate::{default_rule_id, Command, RuleCorrection, SessionMetadata};
+use lazy_static::lazy_static;
+use regex::Regex;
+
+lazy_static! {
+    // e.g. `git@github.com: Permission denied (publickey).`
+    static ref SSH_RE: Regex =
+        Regex::new(r"(?i)permission denied \(publickey|host key verification failed").unwrap();
+    // e.g. `remote: Support for password authentication was removed on August 13, 2021.`,
+    // `fatal: Authentication failed for 'https://github.com/org/repo.git/'` or
+    // `fatal: could not read Username for 'https://github.com': terminal prompts disabled`.
+    static ref HTTPS_RE: Regex = Regex::new(
+        r"(?i)support for password authentication was removed|authentication failed for '|could not read username for '"
+    )
+    .unwrap();
+    // Hosts hide private repositories the credentials (or key) don't have access to, e.g.
+    // `remote: Repository not found.` or `ERROR: Repository not found.`
+    static ref NOT_FOUND_RE: Regex =
+        Regex::new(r"(?i)repository not found|repository '[^']*' not found").unwrap();
+    // e.g. `fatal: repository 'https://github.com/org/repo.git/' not found`.
+    static ref URL_RE: Regex = Regex::new(r"'((?:https?|ssh)://[^']+)'").unwrap();
+}
+
+// The subcommands that talk to a remote.
+const SUBCOMMANDS: &[&str] = &["clone", "fetch", "ls-remote", "pull", "push"];
+
+/*
+Switches a remote between HTTPS and SSH when authenticating with one of them fails, e.g.
+`git push` => `git remote set-url origin git@github.com:org/repo.git && git push` after
+`Support for password authentication was removed`. The URL comes from the repository's
+configuration (or the output). For `git clone`, the URL is replaced in the command.
+*/
+pub(crate) struct GitRemoteProtocol;
+impl Rule for GitRemoteProtocol {
+    default_rule_id!(GitRemoteProtocol);
+
+    fn matches(&self, command: &Command, session_metadata: &SessionMetadata) -> bool {
+        runs_any(command, session_metadata, SUBCOMMANDS)
+            && [&*SSH_RE, &*HTTPS_RE, &*NOT_FOUND_RE]
+                .iter()
+                .any(|re| re.is_match(command.output))
+    }
+
+    fn generate_command_corrections<'a>(
+        &self,
+        command: &'a Command,
+        session_metadata: &'a SessionMetadata,
+    ) -> Option<Vec<RuleCorrection<'a>>> {
+        let git = GitInvocation::parse(command, session_metadata)?;
+
+        if git.subcommand() == "clone" {
+            let url = git
+                .args()
+                .iter()
+                .find(|arg| RemoteUrl::parse(arg).is_some())?;
+            let switched = switched(url, command.output)?;
+            return new_commands_from_suggestions([switched], command.input_parts(), url);
+        }
+
+        let git_state = session_metadata.git_state(command);
+        let remote = remote_of(&git, git_state);
+        let url = match git_state.remote_urls.get(&remote) {
+            Some(url) => url.clone(),
+            None => URL_RE.captures(command.output)?.get(1)?.as_str().to_owned(),
+        };
+        let set_url = git.run([
+            "remote".to_owned(),
+            "set-url".to_owned(),
+            remote,
+            switched(&url, command.output)?,
+        ]);
+        Some(vec![RuleCorrection::and(set_url, command.input_parts())])
+    }
+}
+
+/// The URL in the other protocol, if that protocol could fix the failure.
+fn switched(url: &str, output: &str) -> Option<String> {
+    let remote_url = RemoteUrl::parse(url)?;
+    let is_ssh = RemoteUrl::is_ssh(url);
+    if is_ssh && (SSH_RE.is_match(output) || NOT_FOUND_RE.is_match(output)) {
+        Some(remote_url.to_https())
+    } else if !is_ssh && (HTTPS_RE.is_match(output) || NOT_FOUND_RE.is_match(output)) {
+        Some(remote_url.to_ssh())
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::git::GitState;
+    use crate::test_utils::{basic_corrections, regular_corrections};
+    use crate::{Command, ExitCode, SessionMetadata};
+
+    #[test]
+    fn test_ssh_to_https() {
+        let mut session_metadata = SessionMetadata::new();
+        session_metadata.set_git_state(GitState {
+            remotes: vec!["origin".to_owned(), "fork".to_owned()],
+            remote_urls: [("fork".to_owned(), "git@github.com:me/repo.git".to_owned())]
+                .into_iter()
+                .collect(),
+            ..Default::default()
+        });
+        let command = Command::new(
+            "git push fork main",
+            "git@github.com: Permission denied (publickey).
+fatal: Could not read from remote repository.
+
+Please make sure you have the correct access rights
+and the repository exists.",
+            ExitCode(128),
+        );
+        assert_eq!(
+            regular_corrections(command, &session_metadata),
+            vec!["git remote set-url fork https://github.com/me/repo.git && git push fork main"]
+        );
+    }
+
+    #[test]
+    fn test_clone() {
+        assert_eq!(
+            basic_corrections(
+                "git clone https://github.com/org/private.git",
+                "Cloning into 'private'...
+remote: Repository not found.
+fatal: repository 'https://github.com/org/private.git/' not found"
+            ),
+            vec!["git clone git@github.com:org/private.git"]
+        );
+    }
+}
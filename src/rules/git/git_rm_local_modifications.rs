@@ -0,0 +1,52 @@
+use crate::rules::git::invocation::{runs_any, GitInvocation};
+use crate::rules::Rule;
+use crate::{default_rule_id, Command, RuleCorrection, SessionMetadata};
+
+/// Fixes a `git rm` of files with local (or staged) changes, by either keeping the files and
+/// only untracking them (`--cached`) or forcing the removal (`-f`), like git suggests.
+pub(crate) struct GitRmLocalModifications;
+impl Rule for GitRmLocalModifications {
+    default_rule_id!(GitRmLocalModifications);
+
+    fn matches(&self, command: &Command, session_metadata: &SessionMetadata) -> bool {
+        let lowercase_output = command.lowercase_output();
+        runs_any(command, session_metadata, &["rm"])
+            && (lowercase_output.contains("has local modifications")
+                || lowercase_output.contains("have local modifications")
+                || lowercase_output.contains("staged in the index"))
+            && lowercase_output.contains("use --cached to keep the file")
+    }
+
+    fn generate_command_corrections<'a>(
+        &self,
+        command: &'a Command,
+        session_metadata: &'a SessionMetadata,
+    ) -> Option<Vec<RuleCorrection<'a>>> {
+        let git = GitInvocation::parse(command, session_metadata)?;
+        Some(
+            ["--cached", "-f"]
+                .iter()
+                .map(|option| git.with_flag(option).into())
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::basic_corrections;
+
+    #[test]
+    fn test_staged_changes() {
+        assert_eq!(
+            basic_corrections(
+                "git rm -r docs",
+                "error: the following files have changes staged in the index:
+    docs/index.md
+    docs/setup.md
+(use --cached to keep the file, or -f to force removal)"
+            ),
+            vec!["git rm --cached -r docs", "git rm -f -r docs"]
+        );
+    }
+}
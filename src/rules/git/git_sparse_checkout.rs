@@ -0,0 +1,98 @@
+use std::path::Path;
+
+use crate::rules::git::invocation::{runs_any, GitInvocation};
+use crate::rules::Rule;
+use crate::{default_rule_id, Command, RuleCorrection, SessionMetadata};
+use itertools::Itertools;
+use lazy_static::lazy_static;
+use regex::Regex;
+
+lazy_static! {
+    // e.g. `The following paths and/or pathspecs matched paths that exist
+    // outside of your sparse-checkout definition, so will not be
+    // updated in the index:`, followed by the paths (and hints).
+    static ref RE: Regex = Regex::new(
+        r"(?is)outside of your sparse-checkout definition.*?updated in the index:|outside the current sparse checkout:"
+    )
+    .unwrap();
+}
+
+// The subcommands that refuse paths outside of the sparse-checkout, unless given `--sparse`.
+const SUBCOMMANDS: &[&str] = &["add", "mv", "rm"];
+
+/*
+Fixes `git add`/`rm`/`mv` on paths outside of the sparse-checkout, either by adding their
+directories to it (`git sparse-checkout add docs && git add docs/guide.md`) or, like git suggests,
+with `--sparse`.
+*/
+pub(crate) struct GitSparseCheckout;
+impl Rule for GitSparseCheckout {
+    default_rule_id!(GitSparseCheckout);
+
+    fn matches(&self, command: &Command, session_metadata: &SessionMetadata) -> bool {
+        runs_any(command, session_metadata, SUBCOMMANDS) && RE.is_match(command.output)
+    }
+
+    fn generate_command_corrections<'a>(
+        &self,
+        command: &'a Command,
+        session_metadata: &'a SessionMetadata,
+    ) -> Option<Vec<RuleCorrection<'a>>> {
+        let git = GitInvocation::parse(command, session_metadata)?;
+        let paths = &command.output[RE.find(command.output)?.end()..];
+        // Cone mode (the default) only includes whole directories.
+        let directories = paths
+            .lines()
+            .map(str::trim)
+            .skip_while(|line| line.is_empty())
+            .take_while(|line| !line.is_empty() && !line.starts_with("hint:"))
+            .map(|path| match Path::new(path).parent() {
+                Some(parent) if !parent.as_os_str().is_empty() => parent.to_string_lossy(),
+                _ => path.into(),
+            })
+            .map(|directory| directory.into_owned())
+            .unique()
+            .collect_vec();
+        if directories.is_empty() {
+            return None;
+        }
+
+        let sparse_checkout_add = git.run(
+            ["sparse-checkout", "add"]
+                .map(str::to_owned)
+                .into_iter()
+                .chain(directories),
+        );
+        Some(vec![
+            RuleCorrection::and(sparse_checkout_add, command.input_parts()),
+            git.with_flag("--sparse").into(),
+        ])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::basic_corrections;
+
+    #[test]
+    fn test_add() {
+        assert_eq!(
+            basic_corrections(
+                "git add docs/guide.md docs/api/index.md",
+                "The following paths and/or pathspecs matched paths that exist
+outside of your sparse-checkout definition, so will not be
+updated in the index:
+docs/guide.md
+docs/api/index.md
+hint: If you intend to update such entries, try one of the following:
+hint: * Use the --sparse option.
+hint: * Disable or modify the sparsity rules.
+hint: Disable this message with \"git config advice.updateSparsePath false\""
+            ),
+            vec![
+                "git sparse-checkout add docs docs/api && git add docs/guide.md docs/api/index.md",
+                "git add --sparse docs/guide.md docs/api/index.md"
+            ]
+        );
+    }
+}
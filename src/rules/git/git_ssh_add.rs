@@ -0,0 +1,81 @@
+use crate::rules::Rule;
+use crate::{default_rule_id, Command, RuleCorrection, SessionMetadata};
+use lazy_static::lazy_static;
+use regex::Regex;
+
+lazy_static! {
+    // `The agent has no identities.` is only printed with `ssh -v` (or `GIT_SSH_COMMAND="ssh -v"`),
+    // while `sign_and_send_pubkey: signing failed ...: agent refused operation` is always printed.
+    static ref NO_IDENTITIES_RE: Regex =
+        Regex::new(r"(?i)the agent has no identities|agent refused operation").unwrap();
+}
+
+/*
+Adds the user's SSH key to the agent (`ssh-add`) when the SSH agent has no identities, e.g.
+`git pull` => `ssh-add && git pull`. This happens after a reboot, or in a new session.
+
+A plain `Permission denied (publickey)` doesn't say whether the agent was the problem (the key may
+just not be registered with the server), so this only matches when ssh also says the agent had no
+usable key. Without `ssh -v` that's only the case when the agent refused to sign; an agent that
+simply has no identities isn't reported, so those failures aren't corrected.
+*/
+pub(crate) struct GitSshAdd;
+impl Rule for GitSshAdd {
+    default_rule_id!(GitSshAdd);
+
+    fn matches(&self, command: &Command, _session_metadata: &SessionMetadata) -> bool {
+        command
+            .lowercase_output()
+            .contains("permission denied (publickey")
+            && NO_IDENTITIES_RE.is_match(command.output)
+    }
+
+    fn generate_command_corrections<'a>(
+        &self,
+        command: &'a Command,
+        _session_metadata: &'a SessionMetadata,
+    ) -> Option<Vec<RuleCorrection<'a>>> {
+        Some(vec![RuleCorrection::and("ssh-add", command.input_parts())])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::basic_corrections;
+
+    #[test]
+    fn test_agent_without_identities() {
+        assert_eq!(
+            basic_corrections(
+                "git pull --rebase",
+                "debug1: Offering public key: /home/me/.ssh/id_ed25519 ED25519 SHA256:abc agent
+debug1: The agent has no identities.
+git@github.com: Permission denied (publickey).
+fatal: Could not read from remote repository."
+            ),
+            vec!["ssh-add && git pull --rebase"]
+        );
+
+        // Without `-v`, the agent refusing to sign is still reported.
+        assert_eq!(
+            basic_corrections(
+                "git push",
+                "sign_and_send_pubkey: signing failed for ED25519 \"/home/me/.ssh/id_ed25519\" from agent: agent refused operation
+git@github.com: Permission denied (publickey).
+fatal: Could not read from remote repository."
+            ),
+            vec!["ssh-add && git push"]
+        );
+    }
+
+    #[test]
+    fn test_permission_denied_without_agent_hint() {
+        assert!(basic_corrections(
+            "git push",
+            "git@github.com: Permission denied (publickey).
+fatal: Could not read from remote repository."
+        )
+        .iter()
+        .all(|correction| !correction.starts_with("ssh-add")));
+    }
+}
@@ -0,0 +1,139 @@
+use crate::rules::git::invocation::{runs_any, GitInvocation};
+use crate::rules::Rule;
+use crate::{default_rule_id, Command, RuleCorrection, SessionMetadata};
+use lazy_static::lazy_static;
+use regex::Regex;
+
+lazy_static! {
+    // e.g. `error: Your local changes to the following files would be overwritten by checkout:`,
+    // `error: your local changes would be overwritten by cherry-pick.` or
+    // `error: cannot pull with rebase: You have unstaged changes.`
+    static ref LOCAL_CHANGES_RE: Regex = Regex::new(
+        r"(?i)your local changes (?:to the following files )?would be overwritten|cannot (?:pull with rebase|rebase): (?:you have unstaged changes|your index contains uncommitted changes)|please commit your changes or stash them"
+    )
+    .unwrap();
+    // e.g. `error: The following untracked working tree files would be overwritten by checkout:`
+    static ref UNTRACKED_RE: Regex =
+        Regex::new(r"(?i)untracked working tree files would be overwritten").unwrap();
+}
+
+// The subcommands local changes can get in the way of.
+const SUBCOMMANDS: &[&str] = &[
+    "checkout",
+    "switch",
+    "pull",
+    "rebase",
+    "merge",
+    "cherry-pick",
+];
+// The subcommands that can stash (and unstash) local changes themselves.
+const AUTOSTASH_SUBCOMMANDS: &[&str] = &["pull", "rebase", "merge"];
+
+/*
+Stashes local changes that get in the way of a checkout, pull, rebase, merge, etc.:
+`git checkout main` => `git stash && git checkout main && git stash pop`.
+Untracked files are stashed too if they're the problem. Where git can do this itself,
+`--autostash` is suggested as well.
+*/
+pub(crate) struct GitStash;
+impl Rule for GitStash {
+    default_rule_id!(GitStash);
+
+    fn matches(&self, command: &Command, session_metadata: &SessionMetadata) -> bool {
+        runs_any(command, session_metadata, SUBCOMMANDS)
+            && (LOCAL_CHANGES_RE.is_match(command.output) || UNTRACKED_RE.is_match(command.output))
+    }
+
+    fn generate_command_corrections<'a>(
+        &self,
+        command: &'a Command,
+        session_metadata: &'a SessionMetadata,
+    ) -> Option<Vec<RuleCorrection<'a>>> {
+        let git = GitInvocation::parse(command, session_metadata)?;
+        let stash = if UNTRACKED_RE.is_match(command.output) {
+            git.run(["stash", "--include-untracked"])
+        } else {
+            git.run(["stash"])
+        };
+        let mut corrections = vec![RuleCorrection::and(
+            stash,
+            RuleCorrection::and(command.input_parts(), git.run(["stash", "pop"])),
+        )];
+
+        // `--autostash` only stashes tracked changes.
+        if git.is_any(AUTOSTASH_SUBCOMMANDS) && !UNTRACKED_RE.is_match(command.output) {
+            corrections.push(git.with_flag("--autostash").into());
+        }
+        Some(corrections)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::basic_corrections;
+
+    #[test]
+    fn test_checkout() {
+        assert_eq!(
+            basic_corrections(
+                "git checkout main",
+                "error: Your local changes to the following files would be overwritten by checkout:
+	src/lib.rs
+Please commit your changes or stash them before you switch branches.
+Aborting"
+            ),
+            vec!["git stash && git checkout main && git stash pop"]
+        );
+        assert_eq!(
+            basic_corrections(
+                "git switch feature",
+                "error: The following untracked working tree files would be overwritten by checkout:
+	notes.txt
+Please move or remove them before you switch branches.
+Aborting"
+            ),
+            vec!["git stash --include-untracked && git switch feature && git stash pop"]
+        );
+    }
+
+    #[test]
+    fn test_autostash() {
+        assert_eq!(
+            basic_corrections(
+                "git pull --rebase",
+                "error: cannot pull with rebase: You have unstaged changes.
+error: Please commit or stash them."
+            ),
+            vec![
+                "git stash && git pull --rebase && git stash pop",
+                "git pull --autostash --rebase"
+            ]
+        );
+        assert_eq!(
+            basic_corrections(
+                "git merge origin/main",
+                "error: Your local changes to the following files would be overwritten by merge:
+	Cargo.toml
+Please commit your changes or stash them before you merge.
+Aborting"
+            ),
+            vec![
+                "git stash && git merge origin/main && git stash pop",
+                "git merge --autostash origin/main"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_cherry_pick() {
+        assert_eq!(
+            basic_corrections(
+                "git cherry-pick abc123",
+                "error: your local changes would be overwritten by cherry-pick.
+hint: commit your changes or stash them to proceed.
+fatal: cherry-pick failed"
+            ),
+            vec!["git stash && git cherry-pick abc123 && git stash pop"]
+        );
+    }
+}
@@ -0,0 +1,44 @@
+use crate::rules::git::invocation::{runs_any, GitInvocation};
+use crate::rules::Rule;
+use crate::{default_rule_id, Command, RuleCorrection, SessionMetadata};
+use lazy_static::lazy_static;
+use regex::Regex;
+
+lazy_static! {
+    static ref RE: Regex = Regex::new("(?i)fatal: tag '[^']*' already exists").unwrap();
+}
+
+/// Moves a tag that already exists (`git tag --force`).
+pub(crate) struct GitTagExists;
+impl Rule for GitTagExists {
+    default_rule_id!(GitTagExists);
+
+    fn matches(&self, command: &Command, session_metadata: &SessionMetadata) -> bool {
+        runs_any(command, session_metadata, &["tag"]) && RE.is_match(command.output)
+    }
+
+    fn generate_command_corrections<'a>(
+        &self,
+        command: &'a Command,
+        session_metadata: &'a SessionMetadata,
+    ) -> Option<Vec<RuleCorrection<'a>>> {
+        let git = GitInvocation::parse(command, session_metadata)?;
+        Some(vec![git.with_flag("--force").into()])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::basic_corrections;
+
+    #[test]
+    fn test_annotated_tag() {
+        assert_eq!(
+            basic_corrections(
+                "git tag -a v1.0 -m 'First release'",
+                "fatal: tag 'v1.0' already exists"
+            ),
+            vec!["git tag --force -a v1.0 -m 'First release'"]
+        );
+    }
+}
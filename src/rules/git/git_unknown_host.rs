@@ -0,0 +1,141 @@
+use crate::rules::git::invocation::GitInvocation;
+use crate::rules::git::remote_url::RemoteUrl;
+use crate::rules::util::{get_single_closest_match, new_commands_from_suggestions};
+use crate::rules::Rule;
+use crate::{default_rule_id, Command, RuleCorrection, SessionMetadata};
+use itertools::Itertools;
+use lazy_static::lazy_static;
+use regex::Regex;
+
+lazy_static! {
+    // e.g. `fatal: unable to access 'https://githb.com/org/repo.git/': Could not resolve host: githb.com`
+    // or `ssh: Could not resolve hostname githb.com: Name or service not known`.
+    static ref RE: Regex = Regex::new(r"(?i)could not resolve host(?:name)?:? ([^\s:']+)").unwrap();
+}
+
+// Hosts (most) remotes are on, besides the ones of the repository's other remotes.
+const KNOWN_HOSTS: &[&str] = &[
+    "bitbucket.org",
+    "codeberg.org",
+    "dev.azure.com",
+    "gitea.com",
+    "github.com",
+    "gitlab.com",
+    "ssh.dev.azure.com",
+];
+
+/*
+Corrects a misspelled host in a remote URL, either in the command (e.g. `git clone`) or in the
+configured remote: `git push` => `git remote set-url origin git@github.com:org/repo.git && git push`.
+*/
+pub(crate) struct GitUnknownHost;
+impl Rule for GitUnknownHost {
+    default_rule_id!(GitUnknownHost);
+
+    fn matches(&self, command: &Command, _session_metadata: &SessionMetadata) -> bool {
+        RE.is_match(command.output)
+    }
+
+    fn generate_command_corrections<'a>(
+        &self,
+        command: &'a Command,
+        session_metadata: &'a SessionMetadata,
+    ) -> Option<Vec<RuleCorrection<'a>>> {
+        let git = GitInvocation::parse(command, session_metadata)?;
+        let unknown = RE.captures(command.output)?.get(1)?.as_str();
+        let git_state = session_metadata.git_state(command);
+
+        let remote_hosts = git_state
+            .remote_urls
+            .values()
+            .filter_map(|url| RemoteUrl::parse(url))
+            .map(|url| url.host)
+            .collect_vec();
+        let hosts = KNOWN_HOSTS
+            .iter()
+            .copied()
+            .chain(remote_hosts.iter().map(String::as_str))
+            .filter(|host| *host != unknown)
+            // A typo only changes a couple of characters, and hosts tend to look alike
+            // (e.g. `git.example.co` and `gitea.com`).
+            .filter(|host| host.len().abs_diff(unknown.len()) <= 2)
+            .unique();
+        let matcher = session_metadata.matcher(self.id());
+        let host = get_single_closest_match(unknown, hosts, matcher)?;
+        let fix = |url: &str| url.replacen(unknown, host, 1);
+
+        // The URL is in the command, e.g. `git clone https://githb.com/org/repo.git`.
+        if let Some(url) = git
+            .args()
+            .iter()
+            .find(|arg| RemoteUrl::parse(arg).is_some_and(|remote_url| remote_url.host == unknown))
+        {
+            return new_commands_from_suggestions([fix(url)], command.input_parts(), url);
+        }
+
+        let (remote, url) = git_state.remote_urls.iter().find(|(_, url)| {
+            RemoteUrl::parse(url).is_some_and(|remote_url| remote_url.host == unknown)
+        })?;
+        let set_url = git.run(["remote", "set-url", remote, &fix(url)]);
+        Some(vec![RuleCorrection::and(set_url, command.input_parts())])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::git::GitState;
+    use crate::test_utils::{basic_corrections, regular_corrections};
+    use crate::{Command, ExitCode, SessionMetadata};
+
+    #[test]
+    fn test_clone() {
+        assert_eq!(
+            basic_corrections(
+                "git clone https://githb.com/org/repo.git",
+                "Cloning into 'repo'...
+fatal: unable to access 'https://githb.com/org/repo.git/': Could not resolve host: githb.com"
+            ),
+            vec!["git clone https://github.com/org/repo.git"]
+        );
+    }
+
+    fn session_metadata(url: &str) -> SessionMetadata<'static> {
+        let mut session_metadata = SessionMetadata::new();
+        session_metadata.set_git_state(GitState {
+            remotes: vec!["origin".to_owned()],
+            remote_urls: [("origin".to_owned(), url.to_owned())]
+                .into_iter()
+                .collect(),
+            ..Default::default()
+        });
+        session_metadata
+    }
+
+    #[test]
+    fn test_configured_remote() {
+        let command = Command::new(
+            "git fetch",
+            "ssh: Could not resolve hostname gitlab.con: Name or service not known
+fatal: Could not read from remote repository.",
+            ExitCode(128),
+        );
+        assert_eq!(
+            regular_corrections(command, &session_metadata("git@gitlab.con:team/app.git")),
+            vec!["git remote set-url origin git@gitlab.com:team/app.git && git fetch"]
+        );
+
+        // `git.example.co` isn't close to any known host.
+        let command = Command::new(
+            "git fetch",
+            "ssh: Could not resolve hostname git.example.co: Name or service not known",
+            ExitCode(128),
+        );
+        assert_eq!(
+            regular_corrections(
+                command,
+                &session_metadata("git@git.example.co:team/app.git")
+            ),
+            Vec::<String>::new()
+        );
+    }
+}
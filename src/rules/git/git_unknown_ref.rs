@@ -0,0 +1,125 @@
+use crate::git::GitState;
+use crate::rules::util::{get_single_closest_match, new_commands_from_suggestions};
+use crate::rules::Rule;
+use crate::{default_rule_id, Command, RuleCorrection, SessionMetadata};
+use itertools::Itertools;
+use lazy_static::lazy_static;
+use regex::Regex;
+
+lazy_static! {
+    // e.g. `error: src refspec v1.O does not match any` for `git push origin v1.O`.
+    static ref PUSH_RE: Regex = Regex::new("(?i)error: src refspec (\\S+) does not match any").unwrap();
+    // e.g. `fatal: ambiguous argument 'v1.0.': unknown revision or path not in the working tree.`
+    // for `git log v1.0.`, or `fatal: invalid upstream 'orgin/main'` for `git rebase orgin/main`.
+    static ref REVISION_RE: Regex = Regex::new(
+        "(?i)fatal: ambiguous argument '([^']*)': unknown revision|fatal: invalid upstream '([^']*)'"
+    )
+    .unwrap();
+    // e.g. `error: tag 'v1.O' not found.` for `git tag -d v1.O`.
+    static ref TAG_RE: Regex = Regex::new("(?i)error: tag '([^']*)' not found").unwrap();
+}
+
+/// Corrects a misspelled branch or tag, e.g. `git push origin v1.O` => `git push origin v1.0`
+/// or `git rebase orgin/main` => `git rebase origin/main`.
+pub(crate) struct GitUnknownRef;
+impl Rule for GitUnknownRef {
+    default_rule_id!(GitUnknownRef);
+
+    fn matches(&self, command: &Command, _session_metadata: &SessionMetadata) -> bool {
+        [&*PUSH_RE, &*REVISION_RE, &*TAG_RE]
+            .iter()
+            .any(|re| re.is_match(command.output))
+    }
+
+    fn generate_command_corrections<'a>(
+        &self,
+        command: &'a Command,
+        session_metadata: &'a SessionMetadata,
+    ) -> Option<Vec<RuleCorrection<'a>>> {
+        let git_state = session_metadata.git_state(command);
+        let (unknown, candidates) = if let Some(captures) = PUSH_RE.captures(command.output) {
+            // Only local refs can be pushed.
+            (captures.get(1)?, local_refs(git_state).collect_vec())
+        } else if let Some(captures) = TAG_RE.captures(command.output) {
+            (captures.get(1)?, git_state.tags.clone())
+        } else {
+            let captures = REVISION_RE.captures(command.output)?;
+            let refs = local_refs(git_state).chain(git_state.qualified_remote_branches());
+            (
+                captures.get(1).or_else(|| captures.get(2))?,
+                refs.collect_vec(),
+            )
+        };
+        let unknown = unknown.as_str();
+
+        let matcher = session_metadata.matcher(self.id());
+        let fix =
+            get_single_closest_match(unknown, candidates.iter().map(String::as_str), matcher)?;
+        new_commands_from_suggestions([fix.to_owned()], command.input_parts(), unknown)
+    }
+}
+
+/// Local branches and tags.
+fn local_refs(git_state: &GitState) -> impl Iterator<Item = String> + '_ {
+    git_state
+        .local_branches
+        .iter()
+        .chain(&git_state.tags)
+        .cloned()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::git::GitState;
+    use crate::test_utils::regular_corrections;
+    use crate::{Command, ExitCode, SessionMetadata};
+
+    fn session_metadata() -> SessionMetadata<'static> {
+        let mut session_metadata = SessionMetadata::new();
+        session_metadata.set_git_state(GitState {
+            local_branches: vec!["main".to_owned(), "feature".to_owned()],
+            remote_branches: [("origin".to_owned(), vec!["main".to_owned()])]
+                .into_iter()
+                .collect(),
+            tags: vec!["v1.0".to_owned(), "v2.0".to_owned()],
+            ..Default::default()
+        });
+        session_metadata
+    }
+
+    #[test]
+    fn test_push_tag() {
+        let command = Command::new(
+            "git push origin v1.O",
+            "error: src refspec v1.O does not match any\nerror: failed to push some refs to 'github.com:org/repo.git'",
+            ExitCode(1),
+        );
+        assert_eq!(
+            regular_corrections(command, &session_metadata()),
+            vec!["git push origin v1.0"]
+        );
+    }
+
+    #[test]
+    fn test_revisions() {
+        let command = Command::new(
+            "git rebase orgin/main",
+            "fatal: invalid upstream 'orgin/main'",
+            ExitCode(128),
+        );
+        assert_eq!(
+            regular_corrections(command, &session_metadata()),
+            vec!["git rebase origin/main"]
+        );
+
+        let command = Command::new(
+            "git tag -d v2.O",
+            "error: tag 'v2.O' not found.",
+            ExitCode(1),
+        );
+        assert_eq!(
+            regular_corrections(command, &session_metadata()),
+            vec!["git tag -d v2.0"]
+        );
+    }
+}
@@ -0,0 +1,67 @@
+use crate::rules::util::{get_single_closest_match, new_commands_from_suggestions};
+use crate::rules::Rule;
+use crate::{default_rule_id, Command, RuleCorrection, SessionMetadata};
+use itertools::Itertools;
+use lazy_static::lazy_static;
+use regex::Regex;
+
+lazy_static! {
+    // `git push orign main` fails with `fatal: 'orign' does not appear to be a git repository`,
+    // `git remote remove orign` with `error: No such remote: 'orign'`.
+    static ref RE: Regex = Regex::new(
+        "(?i)fatal: '([^']*)' does not appear to be a git repository|no such remote:? '?([^'\\s]+)"
+    )
+    .unwrap();
+}
+
+/// Corrects a misspelled remote, e.g. `git push orign main` => `git push origin main`.
+pub(crate) struct GitUnknownRemote;
+impl Rule for GitUnknownRemote {
+    default_rule_id!(GitUnknownRemote);
+
+    fn matches(&self, command: &Command, _session_metadata: &SessionMetadata) -> bool {
+        RE.is_match(command.output)
+    }
+
+    fn generate_command_corrections<'a>(
+        &self,
+        command: &'a Command,
+        session_metadata: &'a SessionMetadata,
+    ) -> Option<Vec<RuleCorrection<'a>>> {
+        let unknown = RE
+            .captures(command.output)
+            .and_then(|captures| captures.get(1).or_else(|| captures.get(2)))?
+            .as_str();
+
+        let git_state = session_metadata.git_state(command);
+        let remotes = git_state.all_remotes().collect_vec();
+        let matcher = session_metadata.matcher(self.id());
+        let remote = get_single_closest_match(unknown, remotes, matcher)?;
+        new_commands_from_suggestions([remote.to_owned()], command.input_parts(), unknown)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::git::GitState;
+    use crate::test_utils::regular_corrections;
+    use crate::{Command, ExitCode, SessionMetadata};
+
+    #[test]
+    fn test_remote_remove() {
+        let mut session_metadata = SessionMetadata::new();
+        session_metadata.set_git_state(GitState {
+            remotes: vec!["origin".to_owned(), "upstream".to_owned()],
+            ..Default::default()
+        });
+        let command = Command::new(
+            "git remote remove upstrem",
+            "error: No such remote: 'upstrem'",
+            ExitCode(2),
+        );
+        assert_eq!(
+            regular_corrections(command, &session_metadata),
+            vec!["git remote remove upstream"]
+        );
+    }
+}
@@ -0,0 +1,337 @@
+//! Parses a git command line into its global options (`git -C dir -c key=value`), its
+//! subcommand (with aliases resolved) and the subcommand's arguments, so rules don't
+//! have to look for literal words like `push` in the input.
+
+use crate::{Command, SessionMetadata};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+// Global options that take a separate value, e.g. `git -C dir status`.
+const OPTIONS_WITH_VALUE: &[&str] = &[
+    "-C",
+    "-c",
+    "--git-dir",
+    "--work-tree",
+    "--namespace",
+    "--config-env",
+    "--super-prefix",
+];
+
+// Git's own commands, which can't be shadowed by aliases.
+const BUILTINS: &[&str] = &[
+    "add",
+    "am",
+    "apply",
+    "archive",
+    "bisect",
+    "blame",
+    "branch",
+    "bundle",
+    "checkout",
+    "cherry-pick",
+    "clean",
+    "clone",
+    "commit",
+    "config",
+    "describe",
+    "diff",
+    "fetch",
+    "format-patch",
+    "gc",
+    "grep",
+    "help",
+    "init",
+    "log",
+    "ls-files",
+    "ls-remote",
+    "merge",
+    "mv",
+    "notes",
+    "pull",
+    "push",
+    "rebase",
+    "reflog",
+    "remote",
+    "reset",
+    "restore",
+    "revert",
+    "rm",
+    "shortlog",
+    "show",
+    "sparse-checkout",
+    "stash",
+    "status",
+    "submodule",
+    "switch",
+    "tag",
+    "worktree",
+];
+
+// Aliases can refer to other aliases, but not endlessly.
+const MAX_ALIAS_DEPTH: usize = 10;
+
+/// A git command line, e.g. `git -C repo co -b feature` with `co = checkout`.
+pub(crate) struct GitInvocation<'c> {
+    parts: &'c [String],
+    /// Where the subcommand (or alias) is in `parts`.
+    position: usize,
+    /// The subcommand with aliases resolved, e.g. `checkout` for `co`. Aliases that run a
+    /// shell command (`!...`) can't be resolved and are kept.
+    subcommand: String,
+    /// The arguments the alias adds before the user's, e.g. `-b` for `cob = checkout -b`.
+    alias_args: Vec<String>,
+}
+
+impl<'c> GitInvocation<'c> {
+    pub(crate) fn parse(command: &'c Command, session_metadata: &SessionMetadata) -> Option<Self> {
+        let parts = command.input_parts();
+        if parts.first()? != "git" {
+            return None;
+        }
+
+        let mut position = 1;
+        while let Some(part) = parts.get(position).filter(|part| part.starts_with('-')) {
+            position += if OPTIONS_WITH_VALUE.contains(&part.as_str()) {
+                2
+            } else {
+                1
+            };
+        }
+        let typed = parts.get(position)?;
+
+        let (subcommand, alias_args) =
+            resolve_alias(typed, || session_metadata.git_aliases(command));
+
+        Some(Self {
+            parts,
+            position,
+            subcommand,
+            alias_args,
+        })
+    }
+
+    pub(crate) fn subcommand(&self) -> &str {
+        &self.subcommand
+    }
+
+    pub(crate) fn is_any(&self, subcommands: &[&str]) -> bool {
+        subcommands.contains(&self.subcommand.as_str())
+    }
+
+    /// The arguments the user typed after the subcommand.
+    pub(crate) fn args(&self) -> &'c [String] {
+        &self.parts[self.position + 1..]
+    }
+
+    /// The subcommand's arguments, including the ones its alias adds.
+    pub(crate) fn expanded_args(&self) -> impl Iterator<Item = &str> {
+        self.alias_args
+            .iter()
+            .chain(self.args())
+            .map(String::as_str)
+    }
+
+    /// Another git command with the same global options, e.g. `git -C repo stash pop`
+    /// for `run(["stash", "pop"])`.
+    pub(crate) fn run(&self, args: impl IntoIterator<Item = impl Into<String>>) -> Vec<String> {
+        self.parts[..self.position]
+            .iter()
+            .cloned()
+            .chain(args.into_iter().map(Into::into))
+            .collect()
+    }
+
+    /// The same subcommand (as typed, so aliases are kept) with other arguments.
+    pub(crate) fn with_args(
+        &self,
+        args: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Vec<String> {
+        self.parts[..=self.position]
+            .iter()
+            .cloned()
+            .chain(args.into_iter().map(Into::into))
+            .collect()
+    }
+
+    /// The command with `flag` right after the subcommand, e.g. `git co -b feature` for
+    /// `with_flag("-b")` on `git co feature`.
+    pub(crate) fn with_flag(&self, flag: &str) -> Vec<String> {
+        self.with_args(std::iter::once(flag).chain(self.args().iter().map(String::as_str)))
+    }
+}
+
+/// Where a git command runs: `-C` changes the directory (relative to the previous one, like git
+/// does), and `--git-dir` and `--work-tree` point to the repository directly.
+#[derive(Debug, PartialEq)]
+pub(crate) struct GitLocation {
+    /// The directory git runs in, which relative paths are resolved against.
+    pub(crate) dir: PathBuf,
+    pub(crate) git_dir: Option<PathBuf>,
+    pub(crate) work_tree: Option<PathBuf>,
+}
+
+impl GitLocation {
+    pub(crate) fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            git_dir: None,
+            work_tree: None,
+        }
+    }
+
+    /// Where the command runs, if we know its working directory. Only git commands can change it.
+    pub(crate) fn of(command: &Command) -> Option<Self> {
+        let mut location = Self::new(command.working_dir?);
+        let parts = command.input_parts();
+        if parts.first().map(String::as_str) != Some("git") {
+            return Some(location);
+        }
+
+        let mut position = 1;
+        while let Some(part) = parts.get(position).filter(|part| part.starts_with('-')) {
+            position += 1;
+            let (option, value) = match part.split_once('=') {
+                Some((option, value)) if option.starts_with("--") => (option, Some(value)),
+                _ if OPTIONS_WITH_VALUE.contains(&part.as_str()) => {
+                    position += 1;
+                    (part.as_str(), parts.get(position - 1).map(String::as_str))
+                }
+                _ => (part.as_str(), None),
+            };
+            let Some(value) = value else {
+                continue;
+            };
+            match option {
+                "-C" => location.dir = location.dir.join(value),
+                "--git-dir" => location.git_dir = Some(location.dir.join(value)),
+                "--work-tree" => location.work_tree = Some(location.dir.join(value)),
+                _ => {}
+            }
+        }
+        Some(location)
+    }
+}
+
+/// Whether the command runs one of these git subcommands (possibly through an alias).
+pub(crate) fn runs_any(
+    command: &Command,
+    session_metadata: &SessionMetadata,
+    subcommands: &[&str],
+) -> bool {
+    GitInvocation::parse(command, session_metadata).is_some_and(|git| git.is_any(subcommands))
+}
+
+/// Resolves what was typed as the subcommand into a subcommand and its arguments, e.g.
+/// `checkout` and `-b` for `cob` with `cob = checkout -b`. The aliases are only needed
+/// (and so only read) if what was typed isn't one of git's own commands.
+pub(crate) fn resolve_alias<'h>(
+    typed: &str,
+    aliases: impl FnOnce() -> &'h HashMap<String, String>,
+) -> (String, Vec<String>) {
+    if BUILTINS.contains(&typed) {
+        (typed.to_owned(), vec![])
+    } else {
+        resolve(typed, aliases())
+    }
+}
+
+/// Resolves an alias (and the aliases it refers to) into a subcommand and its arguments.
+fn resolve(typed: &str, aliases: &HashMap<String, String>) -> (String, Vec<String>) {
+    let mut subcommand = typed.to_owned();
+    let mut args = vec![];
+    for _ in 0..MAX_ALIAS_DEPTH {
+        let Some(expansion) = aliases.get(&subcommand) else {
+            break;
+        };
+        if expansion.starts_with('!') {
+            break;
+        }
+        let Some((first, rest)) = shlex::split(expansion)
+            .and_then(|parts| Some((parts.first()?.clone(), parts[1..].to_vec())))
+        else {
+            break;
+        };
+        subcommand = first;
+        args.splice(0..0, rest);
+        if BUILTINS.contains(&subcommand.as_str()) {
+            break;
+        }
+    }
+    (subcommand, args)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::{GitInvocation, GitLocation};
+    use crate::{Command, ExitCode, SessionMetadata};
+
+    #[test]
+    fn test_global_options_and_aliases() {
+        let mut session_metadata = SessionMetadata::new();
+        session_metadata.set_git_aliases([
+            ("co", "checkout"),
+            ("cob", "co -b"),
+            ("p", "push"),
+            ("st", "!git status --short"),
+        ]);
+
+        let command = Command::new(
+            "git -C repo -c core.pager=cat --no-pager cob feature",
+            "",
+            ExitCode(1),
+        );
+        let invocation = GitInvocation::parse(&command, &session_metadata).unwrap();
+        assert_eq!(invocation.subcommand(), "checkout");
+        assert_eq!(invocation.args(), ["feature"]);
+        assert_eq!(
+            invocation.expanded_args().collect::<Vec<_>>(),
+            vec!["-b", "feature"]
+        );
+        assert_eq!(
+            invocation.run(["switch", "feature"]).join(" "),
+            "git -C repo -c core.pager=cat --no-pager switch feature"
+        );
+        assert_eq!(
+            invocation.with_flag("--track").join(" "),
+            "git -C repo -c core.pager=cat --no-pager cob --track feature"
+        );
+
+        let command = Command::new("git st", "", ExitCode(1));
+        let invocation = GitInvocation::parse(&command, &session_metadata).unwrap();
+        assert_eq!(invocation.subcommand(), "st");
+
+        // Builtins can't be shadowed.
+        session_metadata.set_git_aliases([("push", "pull")]);
+        let command = Command::new("git push", "", ExitCode(1));
+        let invocation = GitInvocation::parse(&command, &session_metadata).unwrap();
+        assert_eq!(invocation.subcommand(), "push");
+
+        let command = Command::new("git --no-pager", "", ExitCode(1));
+        assert!(GitInvocation::parse(&command, &session_metadata).is_none());
+    }
+
+    #[test]
+    fn test_location() {
+        let location = |input| {
+            let command = Command::new(input, "", ExitCode(1)).set_working_dir("/home/me");
+            GitLocation::of(&command).unwrap()
+        };
+        assert_eq!(location("git status"), GitLocation::new("/home/me"));
+        assert_eq!(
+            location("git -C app -c core.pager=cat -C sub status").dir,
+            Path::new("/home/me/app/sub")
+        );
+        assert_eq!(
+            location("git -C /tmp/app --git-dir=.git.bak --work-tree ../tree status"),
+            GitLocation {
+                dir: "/tmp/app".into(),
+                git_dir: Some("/tmp/app/.git.bak".into()),
+                work_tree: Some("/tmp/app/../tree".into()),
+            }
+        );
+        // Only git's own `-C`.
+        assert_eq!(location("make -C app").dir, Path::new("/home/me"));
+    }
+}
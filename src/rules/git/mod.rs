@@ -1,15 +1,47 @@
 use crate::rules::git::{
-    git_checkout::GitCheckout, git_command_not_found::GitCommandNotFound,
+    git_add_pathspec::GitAddPathspec, git_branch_delete_unmerged::GitBranchDeleteUnmerged,
+    git_branch_exists::GitBranchExists, git_checkout::GitCheckout,
+    git_command_not_found::GitCommandNotFound, git_commit_nothing_staged::GitCommitNothingStaged,
+    git_diff_staged::GitDiffStaged, git_lfs_install::GitLfsInstall, git_lfs_migrate::GitLfsMigrate,
+    git_merge_unknown_branch::GitMergeUnknownBranch,
+    git_operation_in_progress::GitOperationInProgress, git_pull_divergent::GitPullDivergent,
     git_push_force::GitPushForce, git_push_set_upstream::GitPushSetUpstream,
+    git_remote_protocol::GitRemoteProtocol, git_rm_local_modifications::GitRmLocalModifications,
+    git_sparse_checkout::GitSparseCheckout, git_ssh_add::GitSshAdd, git_stash::GitStash,
+    git_tag_exists::GitTagExists, git_unknown_host::GitUnknownHost, git_unknown_ref::GitUnknownRef,
+    git_unknown_remote::GitUnknownRemote,
 };
 use crate::rules::Rule;
 
 use super::CommandGroup;
 
+pub(crate) use invocation::{resolve_alias, GitLocation};
+
+mod git_add_pathspec;
+mod git_branch_delete_unmerged;
+mod git_branch_exists;
 mod git_checkout;
 mod git_command_not_found;
+mod git_commit_nothing_staged;
+mod git_diff_staged;
+mod git_lfs_install;
+mod git_lfs_migrate;
+mod git_merge_unknown_branch;
+mod git_operation_in_progress;
+mod git_pull_divergent;
 mod git_push_force;
 mod git_push_set_upstream;
+mod git_remote_protocol;
+mod git_rm_local_modifications;
+mod git_sparse_checkout;
+mod git_ssh_add;
+mod git_stash;
+mod git_tag_exists;
+mod git_unknown_host;
+mod git_unknown_ref;
+mod git_unknown_remote;
+mod invocation;
+mod remote_url;
 
 pub(crate) fn command_group() -> CommandGroup {
     CommandGroup {
@@ -19,6 +51,25 @@ pub(crate) fn command_group() -> CommandGroup {
             GitCheckout.to_arc(),
             GitPushSetUpstream.to_arc(),
             GitPushForce.to_arc(),
+            GitOperationInProgress.to_arc(),
+            GitStash.to_arc(),
+            GitAddPathspec.to_arc(),
+            GitCommitNothingStaged.to_arc(),
+            GitBranchDeleteUnmerged.to_arc(),
+            GitBranchExists.to_arc(),
+            GitPullDivergent.to_arc(),
+            GitRmLocalModifications.to_arc(),
+            GitDiffStaged.to_arc(),
+            GitTagExists.to_arc(),
+            GitMergeUnknownBranch.to_arc(),
+            GitUnknownRemote.to_arc(),
+            GitUnknownRef.to_arc(),
+            GitSshAdd.to_arc(),
+            GitRemoteProtocol.to_arc(),
+            GitUnknownHost.to_arc(),
+            GitLfsInstall.to_arc(),
+            GitLfsMigrate.to_arc(),
+            GitSparseCheckout.to_arc(),
         ],
     }
 }
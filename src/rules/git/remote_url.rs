@@ -0,0 +1,104 @@
+//! Remote URLs in their HTTPS and SSH forms, e.g. `https://github.com/org/repo.git` and
+//! `git@github.com:org/repo.git`.
+
+use crate::git::GitState;
+use crate::rules::git::invocation::GitInvocation;
+
+#[derive(Debug, PartialEq)]
+pub(crate) struct RemoteUrl {
+    pub(crate) host: String,
+    /// e.g. `org/repo.git`.
+    pub(crate) path: String,
+}
+
+impl RemoteUrl {
+    /// Parses HTTPS URLs, `ssh://` URLs and SCP-like SSH URLs (`git@github.com:org/repo.git`).
+    /// Local paths and other protocols aren't remote URLs we can convert.
+    pub(crate) fn parse(url: &str) -> Option<Self> {
+        let (host, path) = if let Some(rest) = url
+            .strip_prefix("https://")
+            .or_else(|| url.strip_prefix("http://"))
+            .or_else(|| url.strip_prefix("ssh://"))
+        {
+            let (authority, path) = rest.split_once('/')?;
+            let host = authority.rsplit('@').next()?;
+            // Ports are specific to the protocol, so they can't be kept anyway.
+            (host.split(':').next()?, path)
+        } else {
+            let (user_host, path) = url.split_once(':')?;
+            let (_, host) = user_host.split_once('@')?;
+            (host, path)
+        };
+
+        let path = path.trim_matches('/');
+        if host.is_empty() || path.is_empty() || host.contains('/') {
+            return None;
+        }
+        Some(Self {
+            host: host.to_owned(),
+            path: path.to_owned(),
+        })
+    }
+
+    pub(crate) fn is_ssh(url: &str) -> bool {
+        !url.starts_with("https://") && !url.starts_with("http://")
+    }
+
+    pub(crate) fn to_ssh(&self) -> String {
+        format!("git@{}:{}", self.host, self.path)
+    }
+
+    pub(crate) fn to_https(&self) -> String {
+        format!("https://{}/{}", self.host, self.path)
+    }
+}
+
+/// The remote a `push`, `pull` or `fetch` talks to: the one given as an argument, the upstream
+/// of the current branch, the only remote, or `origin`.
+pub(crate) fn remote_of(git: &GitInvocation, git_state: &GitState) -> String {
+    let remotes = git_state.all_remotes().collect::<Vec<_>>();
+    if let Some(remote) = git
+        .args()
+        .iter()
+        .filter(|arg| !arg.starts_with('-'))
+        .find(|arg| remotes.contains(&arg.as_str()))
+    {
+        return remote.clone();
+    }
+
+    let upstream = git_state
+        .current_branch
+        .as_ref()
+        .and_then(|branch| git_state.upstreams.get(branch));
+    match (upstream, remotes.as_slice()) {
+        (Some(upstream), _) => upstream.remote.clone(),
+        (None, [remote]) => (*remote).to_owned(),
+        _ => "origin".to_owned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RemoteUrl;
+
+    #[test]
+    fn test_parse() {
+        let url = RemoteUrl {
+            host: "github.com".to_owned(),
+            path: "org/repo.git".to_owned(),
+        };
+        for input in [
+            "https://github.com/org/repo.git/",
+            "https://user@github.com/org/repo.git",
+            "ssh://git@github.com:22/org/repo.git",
+            "git@github.com:org/repo.git",
+        ] {
+            assert_eq!(RemoteUrl::parse(input).as_ref(), Some(&url), "{input}");
+        }
+        assert_eq!(url.to_ssh(), "git@github.com:org/repo.git");
+        assert_eq!(url.to_https(), "https://github.com/org/repo.git");
+
+        assert_eq!(RemoteUrl::parse("../repo"), None);
+        assert_eq!(RemoteUrl::parse("C:/repo"), None);
+    }
+}
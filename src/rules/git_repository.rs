@@ -0,0 +1,502 @@
+//! Reads the state of a git repository (branches, remotes, tags, `HEAD` and in-progress
+//! operations) straight from its `.git` directory, so rules don't have to run git.
+//! Only what rules need is read: no objects, and only the `remote`, `branch` and `alias`
+//! config sections.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::{env, fs};
+
+use crate::git::{GitOperation, GitState, Upstream};
+use crate::rules::GitLocation;
+
+/// The state of the repository a git command at `location` works on, if any.
+pub(crate) fn read(location: &GitLocation) -> Option<GitState> {
+    let repository = Repository::locate(location)?;
+    let refs = repository.refs();
+    let config = repository.config();
+
+    let refs_under = |prefix: &str| {
+        refs.iter()
+            .filter_map(|name| name.strip_prefix(prefix))
+            .map(str::to_owned)
+            .collect::<Vec<_>>()
+    };
+
+    let mut remote_branches: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for remote_branch in refs_under("refs/remotes/") {
+        // Both remote and branch names can contain a `/`, so look for the remote among the
+        // configured ones (the longest that matches, for `origin` and `origin/team`). Refs of
+        // remotes that were removed from the config are split at the first `/`.
+        let configured_remote = config
+            .remotes
+            .iter()
+            .filter_map(|remote| {
+                let branch = remote_branch
+                    .strip_prefix(remote.as_str())?
+                    .strip_prefix('/')?;
+                Some((remote.as_str(), branch))
+            })
+            .max_by_key(|(remote, _)| remote.len());
+        let Some((remote, branch)) = configured_remote.or_else(|| remote_branch.split_once('/'))
+        else {
+            continue;
+        };
+        if branch != "HEAD" {
+            remote_branches
+                .entry(remote.to_owned())
+                .or_default()
+                .push(branch.to_owned());
+        }
+    }
+
+    Some(GitState {
+        current_branch: repository.current_branch(),
+        local_branches: refs_under("refs/heads/"),
+        remotes: config.remotes,
+        remote_urls: config.remote_urls,
+        remote_branches,
+        tags: refs_under("refs/tags/"),
+        upstreams: config.upstreams,
+        operation: repository.operation(),
+    })
+}
+
+struct Config {
+    remotes: Vec<String>,
+    remote_urls: BTreeMap<String, String>,
+    upstreams: BTreeMap<String, Upstream>,
+}
+
+struct Repository {
+    /// The repository's (or worktree's) own git dir, with `HEAD` and in-progress operations.
+    git_dir: PathBuf,
+    /// Where refs and config are shared between worktrees. The same as `git_dir`, except in
+    /// linked worktrees.
+    common_dir: PathBuf,
+    /// The directory that contains `.git`.
+    work_tree: PathBuf,
+}
+
+impl Repository {
+    /// The repository a git command at `location` works on: the one given by `--git-dir`, or the
+    /// one that contains the directory git runs in.
+    fn locate(location: &GitLocation) -> Option<Self> {
+        let mut repository = match &location.git_dir {
+            // Without `--work-tree`, the work tree is the directory git runs in.
+            Some(git_dir) => Self::at(git_dir.clone(), location.dir.clone())?,
+            None => Self::find(&location.dir)?,
+        };
+        if let Some(work_tree) = &location.work_tree {
+            repository.work_tree = work_tree.clone();
+        }
+        Some(repository)
+    }
+
+    /// Looks for `.git` in `working_dir` and its parents. `.git` is either the git dir or,
+    /// in worktrees and submodules, a file pointing to it (`gitdir: <path>`).
+    fn find(working_dir: &Path) -> Option<Self> {
+        let dot_git = working_dir
+            .ancestors()
+            .map(|dir| dir.join(".git"))
+            .find(|dot_git| dot_git.exists())?;
+
+        let work_tree = dot_git.parent()?.to_owned();
+        let git_dir = if dot_git.is_dir() {
+            dot_git
+        } else {
+            let contents = fs::read_to_string(&dot_git).ok()?;
+            let path = contents.trim().strip_prefix("gitdir:")?.trim();
+            work_tree.join(path)
+        };
+        Self::at(git_dir, work_tree)
+    }
+
+    fn at(git_dir: PathBuf, work_tree: PathBuf) -> Option<Self> {
+        if !git_dir.is_dir() {
+            return None;
+        }
+        let common_dir = match fs::read_to_string(git_dir.join("commondir")) {
+            Ok(path) => git_dir.join(path.trim()),
+            Err(_) => git_dir.clone(),
+        };
+        Some(Self {
+            git_dir,
+            common_dir,
+            work_tree,
+        })
+    }
+
+    fn current_branch(&self) -> Option<String> {
+        let head = fs::read_to_string(self.git_dir.join("HEAD")).ok()?;
+        head.trim()
+            .strip_prefix("ref: refs/heads/")
+            .map(str::to_owned)
+    }
+
+    /// The names of all refs (e.g. `refs/heads/main`), loose and packed, sorted.
+    fn refs(&self) -> Vec<String> {
+        let mut refs = vec![];
+        loose_refs(&self.common_dir.join("refs"), "refs", &mut refs);
+
+        if let Ok(packed_refs) = fs::read_to_string(self.common_dir.join("packed-refs")) {
+            // `<sha> <name>` lines, plus comments and `^<sha>` lines for peeled tags.
+            refs.extend(
+                packed_refs
+                    .lines()
+                    .filter(|line| !line.starts_with(['#', '^']))
+                    .filter_map(|line| line.split_once(' '))
+                    .map(|(_, name)| name.trim().to_owned()),
+            );
+        }
+
+        refs.sort();
+        refs.dedup();
+        refs
+    }
+
+    /// The remotes (and their URLs) and the upstreams of branches, from `[remote "<name>"]`
+    /// and `[branch "<name>"]`.
+    fn config(&self) -> Config {
+        let mut remotes = vec![];
+        let mut remote_urls = BTreeMap::new();
+        let mut branches: BTreeMap<String, (Option<String>, Option<String>)> = BTreeMap::new();
+        for entry in read_config(self.common_dir.join("config")) {
+            match (entry.section.as_str(), entry.subsection) {
+                ("remote", Some(remote)) => {
+                    if entry.key == "url" {
+                        remote_urls.insert(remote.clone(), entry.value);
+                    }
+                    if !remotes.contains(&remote) {
+                        remotes.push(remote);
+                    }
+                }
+                ("branch", Some(branch)) => {
+                    let (remote, merge) = branches.entry(branch).or_default();
+                    match entry.key.as_str() {
+                        "remote" => *remote = Some(entry.value),
+                        "merge" => *merge = Some(entry.value),
+                        _ => {}
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let upstreams = branches
+            .into_iter()
+            .filter_map(|(branch, (remote, merge))| {
+                let merge = merge?;
+                let upstream = Upstream {
+                    remote: remote?,
+                    branch: merge
+                        .strip_prefix("refs/heads/")
+                        .unwrap_or(&merge)
+                        .to_owned(),
+                };
+                Some((branch, upstream))
+            })
+            .collect();
+        Config {
+            remotes,
+            remote_urls,
+            upstreams,
+        }
+    }
+
+    /// The operation that's in progress (and waiting for the user), if any.
+    fn operation(&self) -> Option<GitOperation> {
+        let exists = |name: &str| self.git_dir.join(name).exists();
+        if exists("rebase-merge") {
+            Some(GitOperation::Rebase)
+        } else if exists("rebase-apply") {
+            // `git am` also uses `rebase-apply`, but leaves an `applying` file.
+            if exists("rebase-apply/applying") {
+                Some(GitOperation::Am)
+            } else {
+                Some(GitOperation::Rebase)
+            }
+        } else if exists("MERGE_HEAD") {
+            Some(GitOperation::Merge)
+        } else if exists("CHERRY_PICK_HEAD") {
+            Some(GitOperation::CherryPick)
+        } else if exists("REVERT_HEAD") {
+            Some(GitOperation::Revert)
+        } else if exists("BISECT_LOG") {
+            Some(GitOperation::Bisect)
+        } else {
+            None
+        }
+    }
+}
+
+/// Collects the names of the loose refs under `dir` (recursively, since ref names can
+/// contain `/`).
+fn loose_refs(dir: &Path, prefix: &str, refs: &mut Vec<String>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let Some(name) = entry
+            .file_name()
+            .to_str()
+            .map(|name| format!("{prefix}/{name}"))
+        else {
+            continue;
+        };
+        let path = entry.path();
+        if path.is_dir() {
+            loose_refs(&path, &name, refs);
+        } else {
+            refs.push(name);
+        }
+    }
+}
+
+/// The aliases from the user's configuration and the configuration of the repository a git
+/// command at `location` works on (if any), e.g. `co` => `checkout`.
+pub(crate) fn read_aliases(location: &GitLocation) -> HashMap<String, String> {
+    let home = env::var_os("HOME").map(PathBuf::from);
+    let xdg_config_home = env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| Some(home.as_ref()?.join(".config")));
+    let user_config_files = [
+        xdg_config_home.map(|dir| dir.join("git/config")),
+        home.map(|home| home.join(".gitconfig")),
+    ];
+    read_aliases_from(user_config_files.into_iter().flatten(), location)
+}
+
+/// Same as `read_aliases`, with the given user configuration files (later files take precedence).
+fn read_aliases_from(
+    user_config_files: impl IntoIterator<Item = PathBuf>,
+    location: &GitLocation,
+) -> HashMap<String, String> {
+    let repository_config =
+        Repository::locate(location).map(|repository| repository.common_dir.join("config"));
+    user_config_files
+        .into_iter()
+        .chain(repository_config)
+        .flat_map(read_config)
+        .filter(|entry| entry.section == "alias")
+        .map(|entry| (entry.key, entry.value))
+        .collect()
+}
+
+/// The paths of the submodules (listed in `.gitmodules`) of the repository that contains
+/// `working_dir`.
+pub(crate) fn read_submodules(working_dir: impl AsRef<Path>) -> Vec<PathBuf> {
+    let Some(repository) = Repository::find(working_dir.as_ref()) else {
+        return vec![];
+    };
+    read_config(repository.work_tree.join(".gitmodules"))
+        .into_iter()
+        .filter(|entry| entry.section == "submodule" && entry.key == "path")
+        .map(|entry| repository.work_tree.join(entry.value))
+        .collect()
+}
+
+/// A `key = value` of a git config file, e.g. `url` in `[remote "origin"]`.
+struct ConfigEntry {
+    /// Section and key names are case-insensitive, so they're lowercased.
+    section: String,
+    subsection: Option<String>,
+    key: String,
+    value: String,
+}
+
+/// Reads the entries of a git config file. Only what rules need is supported: no includes,
+/// line continuations or escapes in values.
+fn read_config(path: impl AsRef<Path>) -> Vec<ConfigEntry> {
+    let Ok(config) = fs::read_to_string(path) else {
+        return vec![];
+    };
+
+    let mut entries = vec![];
+    let (mut section, mut subsection) = (String::new(), None);
+    for line in config.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with(['#', ';']) {
+            continue;
+        }
+        if let Some(header) = line.strip_prefix('[').and_then(|line| line.split_once(']')) {
+            (section, subsection) = parse_section(header.0);
+            continue;
+        }
+
+        let (key, value) = line.split_once('=').unwrap_or((line, "true"));
+        let value = value.trim();
+        entries.push(ConfigEntry {
+            section: section.clone(),
+            subsection: subsection.clone(),
+            key: key.trim().to_lowercase(),
+            value: value
+                .strip_prefix('"')
+                .and_then(|value| value.strip_suffix('"'))
+                .unwrap_or(value)
+                .to_owned(),
+        });
+    }
+    entries
+}
+
+/// Splits a section header like `remote "origin"` into its name and subsection.
+fn parse_section(header: &str) -> (String, Option<String>) {
+    match header.split_once(char::is_whitespace) {
+        Some((name, subsection)) => {
+            let subsection = subsection.trim();
+            let subsection = subsection
+                .strip_prefix('"')
+                .and_then(|subsection| subsection.strip_suffix('"'))
+                .unwrap_or(subsection);
+            (name.to_lowercase(), Some(subsection.to_owned()))
+        }
+        // The legacy `[branch.main]` syntax.
+        None => match header.split_once('.') {
+            Some((name, subsection)) => (name.to_lowercase(), Some(subsection.to_owned())),
+            None => (header.to_lowercase(), None),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::Path;
+
+    use tempfile::tempdir;
+
+    use super::{read, read_aliases_from, read_submodules};
+    use crate::git::{GitOperation, Upstream};
+    use crate::rules::GitLocation;
+
+    fn write(path: impl AsRef<Path>, contents: &str) {
+        let path = path.as_ref();
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, contents).unwrap();
+    }
+
+    const SHA: &str = "0123456789abcdef0123456789abcdef01234567";
+
+    #[test]
+    fn test_read() {
+        let repo = tempdir().unwrap();
+        let git_dir = repo.path().join(".git");
+        write(git_dir.join("HEAD"), "ref: refs/heads/feature/login\n");
+        write(git_dir.join("refs/heads/main"), SHA);
+        write(git_dir.join("refs/heads/feature/login"), SHA);
+        write(
+            git_dir.join("refs/remotes/origin/HEAD"),
+            "ref: refs/remotes/origin/main",
+        );
+        write(git_dir.join("refs/remotes/origin/main"), SHA);
+        write(git_dir.join("refs/remotes/team/fork/main"), SHA);
+        write(
+            git_dir.join("packed-refs"),
+            &format!(
+                "# pack-refs with: peeled fully-peeled sorted\n\
+                 {SHA} refs/heads/main\n\
+                 {SHA} refs/remotes/upstream/release/1.0\n\
+                 {SHA} refs/tags/v1.0\n\
+                 ^{SHA}\n"
+            ),
+        );
+        write(
+            git_dir.join("config"),
+            "[core]\n\tbare = false\n\
+             [remote \"origin\"]\n\turl = git@github.com:org/repo.git\n\
+             [remote \"upstream\"]\n\turl = https://github.com/up/repo.git\n\
+             [remote \"team/fork\"]\n\turl = https://github.com/team/repo.git\n\
+             [branch \"main\"]\n\tremote = origin\n\tmerge = refs/heads/main\n\
+             [alias]\n\tCo = checkout\n\tlg = \"log --oneline\"\n",
+        );
+        write(git_dir.join("MERGE_HEAD"), SHA);
+        fs::create_dir_all(repo.path().join("src/nested")).unwrap();
+        write(
+            repo.path().join(".gitmodules"),
+            "[submodule \"vendor/lib\"]\n\tpath = vendor/lib\n\turl = ../lib.git\n",
+        );
+
+        let state = read(&GitLocation::new(repo.path().join("src/nested"))).unwrap();
+        assert_eq!(state.current_branch.as_deref(), Some("feature/login"));
+        assert_eq!(state.local_branches, vec!["feature/login", "main"]);
+        assert_eq!(state.remotes, vec!["origin", "upstream", "team/fork"]);
+        assert_eq!(
+            state.remote_urls.get("origin").map(String::as_str),
+            Some("git@github.com:org/repo.git")
+        );
+        assert_eq!(
+            state.qualified_remote_branches().collect::<Vec<_>>(),
+            vec!["origin/main", "team/fork/main", "upstream/release/1.0"]
+        );
+        assert_eq!(state.remote_branches["team/fork"], vec!["main"]);
+        assert_eq!(state.tags, vec!["v1.0"]);
+        assert_eq!(
+            state.upstreams.get("main"),
+            Some(&Upstream {
+                remote: "origin".to_owned(),
+                branch: "main".to_owned()
+            })
+        );
+        assert_eq!(state.operation, Some(GitOperation::Merge));
+
+        // The repository's aliases take precedence over the user's.
+        let user_config = repo.path().join("user.gitconfig");
+        write(&user_config, "[alias]\n\tco = commit\n\tst = status\n");
+        let aliases = read_aliases_from(
+            [user_config],
+            &GitLocation::new(repo.path().join("src/nested")),
+        );
+        assert_eq!(aliases.get("co").map(String::as_str), Some("checkout"));
+        assert_eq!(aliases.get("st").map(String::as_str), Some("status"));
+        assert_eq!(aliases.get("lg").map(String::as_str), Some("log --oneline"));
+
+        assert_eq!(
+            read_submodules(repo.path().join("src")),
+            vec![repo.path().join("vendor/lib")]
+        );
+    }
+
+    #[test]
+    fn test_worktree() {
+        let root = tempdir().unwrap();
+        let main_git_dir = root.path().join("repo/.git");
+        write(main_git_dir.join("HEAD"), "ref: refs/heads/main\n");
+        write(main_git_dir.join("refs/heads/main"), SHA);
+        write(main_git_dir.join("refs/heads/hotfix"), SHA);
+
+        // A linked worktree has its own HEAD, and shares the refs of the main repository.
+        let worktree_git_dir = main_git_dir.join("worktrees/hotfix");
+        write(worktree_git_dir.join("HEAD"), "ref: refs/heads/hotfix\n");
+        write(worktree_git_dir.join("commondir"), "../..\n");
+        fs::create_dir_all(worktree_git_dir.join("rebase-merge")).unwrap();
+        write(
+            root.path().join("hotfix/.git"),
+            "gitdir: ../repo/.git/worktrees/hotfix\n",
+        );
+
+        let state = read(&GitLocation::new(root.path().join("hotfix"))).unwrap();
+        assert_eq!(state.current_branch.as_deref(), Some("hotfix"));
+        assert_eq!(state.local_branches, vec!["hotfix", "main"]);
+        assert_eq!(state.operation, Some(GitOperation::Rebase));
+
+        // Detached HEAD, outside of a repository.
+        write(main_git_dir.join("HEAD"), SHA);
+        assert_eq!(
+            read(&GitLocation::new(root.path().join("repo")))
+                .unwrap()
+                .current_branch,
+            None
+        );
+        assert_eq!(read(&GitLocation::new(root.path())), None);
+
+        // `--git-dir` points to the repository from outside of it.
+        let location = GitLocation {
+            git_dir: Some(main_git_dir.clone()),
+            ..GitLocation::new(root.path())
+        };
+        assert_eq!(
+            read(&location).unwrap().local_branches,
+            vec!["hotfix", "main"]
+        );
+    }
+}
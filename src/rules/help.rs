@@ -0,0 +1,358 @@
+//! Learns a command's subcommands and flags from its `--help` output, so rules don't have
+//! to rely only on the (possibly outdated) lists in the catalogue.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+
+use lazy_static::lazy_static;
+use regex::Regex;
+
+lazy_static! {
+    // Two or more spaces (or a tab) separate a subcommand or flag from its description.
+    static ref COLUMN_SEPARATOR_RE: Regex = Regex::new(r"\s{2,}|\t").unwrap();
+    static ref SUBCOMMAND_RE: Regex = Regex::new(r"^[A-Za-z0-9][\w:.-]*$").unwrap();
+    static ref FLAG_RE: Regex =
+        Regex::new(r"(?:^|[\s,|\[(])(--?(?:\[no-\])?[A-Za-z0-9?][\w-]*)").unwrap();
+
+    // Parsed help, by command (e.g. `docker compose`) and tool version (if known).
+    static ref CACHE: Mutex<HashMap<CacheKey, Arc<ParsedHelp>>> = Mutex::new(HashMap::new());
+}
+
+type CacheKey = (String, Option<String>);
+
+// The cache is cleared when it's this big, so long-running clients don't keep the help of every
+// command they've ever seen.
+const MAX_CACHED: usize = 64;
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(crate) struct ParsedHelp {
+    pub subcommands: Vec<String>,
+    pub flags: Vec<String>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Section {
+    Commands,
+    PositionalArguments,
+    Other,
+}
+
+impl Section {
+    fn from_header(header: &str) -> Self {
+        let header = header.to_lowercase();
+        if header.contains("positional") {
+            Section::PositionalArguments
+        } else if header.contains("command") {
+            Section::Commands
+        } else {
+            Section::Other
+        }
+    }
+}
+
+/// Parses the usual `--help` layouts (clap, cobra, argparse, click and git's).
+///
+/// Subcommands are the first column of indented lines in a "commands" section (e.g.
+/// `Commands:`, `Available Commands:` or `These are common Git commands ...:`), including
+/// comma-separated aliases or lists (`build, b` or npm's `access, adduser, audit,`).
+/// Flags are taken from any indented line that starts with a `-`.
+pub(crate) fn parse(help_text: &str) -> ParsedHelp {
+    let mut parsed = ParsedHelp::default();
+    let mut section = Section::Other;
+
+    for line in help_text.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+
+        let is_indented = line.starts_with([' ', '\t']);
+        if !is_indented {
+            // Headers end with a `:`. Anything else (e.g. git's "start a working area" groups)
+            // keeps the current section.
+            if trimmed.ends_with(':') {
+                section = Section::from_header(trimmed);
+            }
+            continue;
+        }
+
+        let first_column = COLUMN_SEPARATOR_RE.split(trimmed).next().unwrap_or(trimmed);
+        if first_column.starts_with('-') {
+            parsed.flags.extend(parse_flags(first_column));
+            continue;
+        }
+
+        match section {
+            Section::Commands => parsed.subcommands.extend(parse_subcommands(first_column)),
+            // argparse lists its subcommands as `{install,list}`.
+            Section::PositionalArguments => {
+                if let Some(choices) = first_column
+                    .strip_prefix('{')
+                    .and_then(|choices| choices.strip_suffix('}'))
+                {
+                    parsed.subcommands.extend(parse_subcommands(choices));
+                }
+            }
+            Section::Other => {}
+        }
+    }
+
+    dedup(&mut parsed.subcommands);
+    dedup(&mut parsed.flags);
+    parsed
+}
+
+fn parse_subcommands(column: &str) -> impl Iterator<Item = String> + '_ {
+    column
+        .split(',')
+        // Docker marks plugins with a `*`.
+        .map(|name| name.trim().trim_end_matches('*'))
+        .filter(|name| SUBCOMMAND_RE.is_match(name))
+        .map(str::to_owned)
+}
+
+fn parse_flags(column: &str) -> Vec<String> {
+    FLAG_RE
+        .captures_iter(column)
+        .filter_map(|captures| captures.get(1))
+        .flat_map(|flag| {
+            // git's `--[no-]verify` is both `--verify` and `--no-verify`.
+            let flag = flag.as_str();
+            match flag.split_once("[no-]") {
+                Some((dashes, name)) => {
+                    vec![format!("{dashes}{name}"), format!("{dashes}no-{name}")]
+                }
+                None => vec![flag.to_owned()],
+            }
+        })
+        .collect()
+}
+
+fn dedup(items: &mut Vec<String>) {
+    let mut seen = HashSet::new();
+    items.retain(|item| seen.insert(item.clone()));
+}
+
+/// Parses the help text of a command, reusing the result for the same command and tool version.
+/// Without a version, the result is reused until the cache is full.
+pub(crate) fn parse_cached(
+    command: &str,
+    tool_version: Option<&str>,
+    help_text: impl FnOnce() -> Option<String>,
+) -> Option<Arc<ParsedHelp>> {
+    let key = (command.to_owned(), tool_version.map(str::to_owned));
+    if let Some(parsed) = CACHE.lock().ok()?.get(&key) {
+        return Some(parsed.clone());
+    }
+
+    // Don't hold the lock while getting the help text, which may run the command.
+    let parsed = Arc::new(parse(&help_text()?));
+    let mut cache = CACHE.lock().ok()?;
+    // The help of other versions of the command is outdated.
+    cache.retain(|(cached_command, _), _| cached_command != command);
+    if cache.len() >= MAX_CACHED {
+        cache.clear();
+    }
+    cache.insert(key, parsed.clone());
+    Some(parsed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse, parse_cached};
+
+    fn subcommands(help_text: &str) -> Vec<String> {
+        parse(help_text).subcommands
+    }
+
+    fn flags(help_text: &str) -> Vec<String> {
+        parse(help_text).flags
+    }
+
+    #[test]
+    fn test_clap() {
+        let help_text = "Rust's package manager
+
+Usage: cargo [+toolchain] [OPTIONS] [COMMAND]
+
+Options:
+  -V, --version                  Print version info and exit
+      --list                     List installed commands
+  -v, --verbose...               Use verbose output
+      --color <WHEN>             Coloring: auto, always, never
+
+Commands:
+    build, b    Compile the current package
+    check, c    Analyze the current package and report errors
+    new         Create a new cargo package
+    ...         See all commands with --list
+
+See 'cargo help <command>' for more information on a specific command.";
+        assert_eq!(
+            subcommands(help_text),
+            vec!["build", "b", "check", "c", "new"]
+        );
+        assert_eq!(
+            flags(help_text),
+            vec!["-V", "--version", "--list", "-v", "--verbose", "--color"]
+        );
+    }
+
+    #[test]
+    fn test_cobra() {
+        let help_text = "kubectl controls the Kubernetes cluster manager.
+
+Basic Commands (Beginner):
+  create          Create a resource from a file or from stdin
+  expose          Take a replication controller, service, deployment or pod
+  run             Run a particular image on the cluster
+
+Available Commands:
+  completion  Generate the autocompletion script for the specified shell
+  help        Help about any command
+
+Flags:
+  -h, --help   help for helm
+
+Global Flags:
+      --kube-context string   name of the kubeconfig context to use
+  -n, --namespace string      namespace scope for this request";
+        assert_eq!(
+            subcommands(help_text),
+            vec!["create", "expose", "run", "completion", "help"]
+        );
+        assert_eq!(
+            flags(help_text),
+            vec!["-h", "--help", "--kube-context", "-n", "--namespace"]
+        );
+    }
+
+    #[test]
+    fn test_argparse() {
+        let help_text = "usage: tool [-h] [--foo FOO] {install,list} ...
+
+positional arguments:
+  {install,list}  sub-commands
+    install       Install a package
+    list          List packages
+  path            The path
+
+options:
+  -h, --help      show this help message and exit
+  --foo FOO       foo of the tool";
+        assert_eq!(subcommands(help_text), vec!["install", "list"]);
+        assert_eq!(flags(help_text), vec!["-h", "--help", "--foo"]);
+    }
+
+    #[test]
+    fn test_click() {
+        let help_text = "Usage: cli [OPTIONS] COMMAND [ARGS]...
+
+Options:
+  --version  Show the version and exit.
+  --help     Show this message and exit.
+
+Commands:
+  init  Initialize the project
+  sync  Sync dependencies";
+        assert_eq!(subcommands(help_text), vec!["init", "sync"]);
+        assert_eq!(flags(help_text), vec!["--version", "--help"]);
+    }
+
+    #[test]
+    fn test_git_style() {
+        let help_text = "usage: git [-v | --version] [-h | --help] [-C <path>] [-c <name>=<value>]
+
+These are common Git commands used in various situations:
+
+start a working area (see also: git help tutorial)
+   clone     Clone a repository into a new directory
+   init      Create an empty Git repository or reinitialize an existing one
+
+work on the current change (see also: git help everyday)
+   add       Add file contents to the index
+
+'git help -a' and 'git help -g' list available subcommands and some
+concept guides.";
+        assert_eq!(subcommands(help_text), vec!["clone", "init", "add"]);
+
+        let help_text = "usage: git commit [<options>] [--] <pathspec>...
+
+    -q, --quiet           suppress summary after successful commit
+    -m, --message <message>
+                          commit message
+    --[no-]verify         bypass pre-commit and commit-msg hooks";
+        assert_eq!(
+            flags(help_text),
+            vec![
+                "-q",
+                "--quiet",
+                "-m",
+                "--message",
+                "--verify",
+                "--no-verify"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_npm_and_docker() {
+        let help_text = "npm <command>
+
+All commands:
+
+    access, adduser, audit, bugs, cache, ci, completion,
+    config, dedupe
+
+Specify configs in the ini-formatted file:";
+        assert_eq!(
+            subcommands(help_text),
+            vec![
+                "access",
+                "adduser",
+                "audit",
+                "bugs",
+                "cache",
+                "ci",
+                "completion",
+                "config",
+                "dedupe"
+            ]
+        );
+
+        let help_text = "Usage:  docker [OPTIONS] COMMAND
+
+Common Commands:
+  run         Create and run a new container from an image
+
+Management Commands:
+  compose*    Docker Compose (Docker Inc.)
+
+Global Options:
+      --config string      Location of client config files
+
+Run 'docker COMMAND --help' for more information on a command.";
+        assert_eq!(subcommands(help_text), vec!["run", "compose"]);
+        assert_eq!(flags(help_text), vec!["--config"]);
+    }
+
+    #[test]
+    fn test_parse_cached() {
+        let help_text = || Some("Commands:\n  build  Build it".to_owned());
+        let parsed = parse_cached("test-cached-tool", Some("1.0.0"), help_text).unwrap();
+        assert_eq!(parsed.subcommands, vec!["build"]);
+
+        // The same version isn't parsed again.
+        let parsed = parse_cached("test-cached-tool", Some("1.0.0"), || unreachable!()).unwrap();
+        assert_eq!(parsed.subcommands, vec!["build"]);
+
+        let parsed = parse_cached("test-cached-tool", Some("2.0.0"), || None);
+        assert_eq!(parsed, None);
+
+        // Without a version, the help is cached too.
+        let parsed = parse_cached("test-unversioned-tool", None, help_text).unwrap();
+        assert_eq!(parsed.subcommands, vec!["build"]);
+        let parsed = parse_cached("test-unversioned-tool", None, || unreachable!()).unwrap();
+        assert_eq!(parsed.subcommands, vec!["build"]);
+    }
+}
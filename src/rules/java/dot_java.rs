@@ -24,7 +24,7 @@ impl Rule for DotJava {
         _session_metadata: &'a SessionMetadata,
     ) -> Option<Vec<RuleCorrection<'a>>> {
         let mut replacement = command.input_parts().to_vec();
-        let pos = replacement.iter().position(|p| (p.ends_with(".java")))?;
+        let pos = replacement.iter().position(|p| p.ends_with(".java"))?;
         *replacement.get_mut(pos)? = replacement.get(pos)?.trim_end_matches(".java").to_owned();
 
         Some(vec![replacement.into()])
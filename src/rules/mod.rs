@@ -11,7 +11,6 @@ mod grep;
 mod java;
 mod ls;
 mod mkdir;
-mod npm;
 mod open;
 mod pip;
 mod python;
@@ -21,16 +20,26 @@ mod sudo;
 mod touch;
 mod yarn;
 
+mod catalogue;
+mod git_repository;
+mod help;
+mod unknown_subcommand;
 mod util;
 
+use crate::risk::Risk;
 use crate::{Command, RuleCorrection, SessionMetadata};
 use lazy_static::lazy_static;
 use std::{collections::HashMap, sync::Arc};
+use unknown_subcommand::UnknownSubcommand;
+
+pub(crate) use git::{resolve_alias as resolve_git_alias, GitLocation};
+pub(crate) use git_repository::{read as read_git_repository, read_aliases as read_git_aliases};
+pub(crate) use help::{parse as parse_help, parse_cached as parse_help_cached, ParsedHelp};
 
 lazy_static! {
     // Only used internally by this mod
     static ref COMMAND_GROUPS: Vec<CommandGroup> =
-        vec![
+        with_unknown_subcommand_rules(vec![
             cargo::command_group(),
             cat::command_group(),
             git::command_group(),
@@ -46,16 +55,59 @@ lazy_static! {
             docker::command_group(),
             sed::command_group(),
             pip::command_group(),
-            npm::command_group(),
             yarn::command_group(),
             grep::command_group(),
             python::command_group(),
             conda::command_group(),
             mkdir::command_group(),
-        ];
+        ]);
 
     pub(crate) static ref RULES_BY_COMMAND: Rules = Rules::from_command_groups(COMMAND_GROUPS.iter());
     pub(crate) static ref GENERIC_RULES: Vec<Arc<dyn Rule>> = generic::rules();
+
+}
+
+/// The known subcommands of a command (e.g. `npm` => `install`, `run`, ...), as
+/// listed in the catalogue. Empty if we don't know the command's subcommands.
+pub(crate) fn subcommands(command_name: &str) -> Vec<&'static str> {
+    catalogue::tool(command_name)
+        .map(|tool| tool.command.subcommand_names(None).collect())
+        .unwrap_or_default()
+}
+
+/// The known (top-level) flags of a command, as listed in the catalogue.
+pub(crate) fn flags(command_name: &str) -> &'static [&'static str] {
+    catalogue::tool(command_name).map_or(&[], |tool| tool.command.flags)
+}
+
+/// Adds the `UnknownSubcommand` rule of every tool in the catalogue, either
+/// first in the tool's command group or in a new group.
+fn with_unknown_subcommand_rules(mut command_groups: Vec<CommandGroup>) -> Vec<CommandGroup> {
+    for tool in catalogue::TOOLS {
+        let Some(rule) = UnknownSubcommand::new(tool) else {
+            continue;
+        };
+        match command_groups
+            .iter_mut()
+            .find(|cmd_group| cmd_group.command_names == tool.names)
+        {
+            Some(cmd_group) => cmd_group.rules.insert(0, rule.to_arc()),
+            None => command_groups.push(CommandGroup {
+                command_names: tool.names,
+                rules: vec![rule.to_arc()],
+            }),
+        }
+    }
+    command_groups
+}
+
+/// Every rule (command specific rules first, then generic rules).
+#[cfg(test)]
+pub(crate) fn all_rules() -> impl Iterator<Item = &'static Arc<dyn Rule>> {
+    COMMAND_GROUPS
+        .iter()
+        .flat_map(|cmd_group| cmd_group.rules.iter())
+        .chain(GENERIC_RULES.iter())
 }
 
 /// A list of command names that have common rules. Often times, this will
@@ -118,6 +170,13 @@ pub(crate) trait Rule: Send + Sync {
         command: &'a Command,
         session_metadata: &'a SessionMetadata,
     ) -> Option<Vec<RuleCorrection<'a>>>;
+
+    /// The least risk of this rule's corrections, for rules that know their corrections have side
+    /// effects (e.g. forcing a removal). The risk of a correction is the higher of this and the
+    /// risk of the corrected command (see `risk::classify`).
+    fn risk(&self) -> Risk {
+        Risk::ReadOnly
+    }
 }
 
 #[macro_export]
@@ -68,18 +68,16 @@ impl Rule for OpenDoesNotExist {
                 .iter()
                 .find(|part| path_from_output.ends_with(part))?;
 
-            let corrected_path =
-                correct_path_at_every_level(path_from_input, command.working_dir?, |path| {
-                    path.exists()
-                });
+            let corrected_path = correct_path_at_every_level(
+                path_from_input,
+                command.working_dir?,
+                |path| path.exists(),
+                session_metadata.matcher(self.id()),
+            );
 
             match corrected_path {
                 Some(path) => {
-                    return new_commands_from_suggestions(
-                        [path],
-                        command.input_parts(),
-                        path_from_input,
-                    );
+                    new_commands_from_suggestions([path], command.input_parts(), path_from_input)
                 }
                 None => {
                     let open_arg_string = path_from_output.to_str()?;
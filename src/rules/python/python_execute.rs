@@ -18,7 +18,7 @@ impl Rule for PythonExecute {
             .captures(command.output)
             .and_then(|captures| captures.get(1))
             .map(|regex_match| regex_match.as_str());
-        filename.map_or(false, |f| !f.ends_with(".py"))
+        filename.is_some_and(|f| !f.ends_with(".py"))
     }
 
     fn generate_command_corrections<'a>(
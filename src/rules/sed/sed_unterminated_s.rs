@@ -24,13 +24,57 @@ impl Rule for SedUnterminatedS {
             .position(|p| (p.starts_with("s/") || p.starts_with("-es/")) && !p.ends_with('/'))?;
         new_command.get_mut(s_string_pos)?.push('/');
 
-        Some(vec![new_command.into()])
+        // Editing in place can be previewed by printing the result instead.
+        let Some(in_place) = new_command.iter().position(|p| is_in_place_flag(p)) else {
+            return Some(vec![new_command.into()]);
+        };
+        let mut preview = new_command.clone();
+        preview.remove(in_place);
+        // BSD sed takes the backup suffix as a separate (often empty) argument: `-i ''` or
+        // `-i .bak`. GNU sed doesn't, so the argument can also be the script or another flag.
+        let is_suffix = |part: &String| in_place + 1 != s_string_pos && !part.starts_with('-');
+        if new_command[in_place] == "-i" && preview.get(in_place).is_some_and(is_suffix) {
+            preview.remove(in_place);
+        }
+        Some(vec![RuleCorrection::from(new_command).with_preview(preview)])
     }
 }
 
+/// `-i`, `-i.bak`, `--in-place` or `--in-place=.bak`.
+fn is_in_place_flag(part: &str) -> bool {
+    part == "--in-place" || part.starts_with("--in-place=") || part.starts_with("-i")
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::test_utils::basic_corrections;
+    use crate::test_utils::{basic_corrections, basic_previews};
+
+    #[test]
+    fn test_sed_in_place_preview() {
+        let output = r#"sed: 1: "s/e/d": unterminated substitute in regular expression"#;
+        assert_eq!(
+            basic_previews("sed -i '' 's/e/d' file.txt", output),
+            vec![Some("sed s/e/d/ file.txt".to_owned())]
+        );
+        assert_eq!(
+            basic_previews("sed -i .bak 's/e/d' file.txt", output),
+            vec![Some("sed s/e/d/ file.txt".to_owned())]
+        );
+        assert_eq!(
+            basic_previews("sed -i 's/e/d' file.txt", output),
+            vec![Some("sed s/e/d/ file.txt".to_owned())]
+        );
+        assert_eq!(
+            basic_previews("sed -i -e 's/e/d' file.txt", output),
+            vec![Some("sed -e s/e/d/ file.txt".to_owned())]
+        );
+        assert_eq!(
+            basic_previews("sed --in-place=.bak 's/e/d' file.txt", output),
+            vec![Some("sed s/e/d/ file.txt".to_owned())]
+        );
+        // Without `-i`, sed only prints the result anyway.
+        assert_eq!(basic_previews("sed 's/e/d' file.txt", output), vec![None]);
+    }
 
     #[test]
     fn test_sed_unterminated() {
@@ -60,7 +104,7 @@ mod tests {
                 "sed 's/e f/d' file.txt",
                 r#"sed: 1: "s/e f/d/": unterminated substitute in regular expression"#
             ),
-            vec!["sed \"s/e f/d/\" file.txt"]
+            vec!["sed 's/e f/d/' file.txt"]
         )
     }
 }
@@ -0,0 +1,277 @@
+use itertools::Itertools;
+use regex::Regex;
+
+use crate::rules::catalogue::Tool;
+use crate::rules::util::{get_single_closest_match, new_commands_from_suggestions};
+use crate::rules::Rule;
+use crate::{Command, RuleCorrection, SessionMetadata};
+
+/// Corrects an unknown subcommand (at any level, e.g. `docker compose upp`) of a tool in the
+/// catalogue. Each tool gets its own instance of this rule, with the rule id from the catalogue.
+pub(crate) struct UnknownSubcommand {
+    id: &'static str,
+    tool: &'static Tool,
+    errors: Vec<Regex>,
+}
+
+impl UnknownSubcommand {
+    /// Returns `None` if the tool doesn't use this rule (see `Tool::rule_id`).
+    pub fn new(tool: &'static Tool) -> Option<Self> {
+        Some(Self {
+            id: tool.rule_id?,
+            tool,
+            errors: tool
+                .unknown_subcommand_errors
+                .iter()
+                .map(|pattern| Regex::new(pattern).unwrap())
+                .collect(),
+        })
+    }
+
+    fn unknown_subcommand<'a>(&self, command: &'a Command) -> Option<&'a str> {
+        let unknown = self
+            .errors
+            .iter()
+            .find_map(|error| error.captures(command.output)?.get(1))?;
+        unknown.as_str().split_whitespace().last()
+    }
+}
+
+impl Rule for UnknownSubcommand {
+    fn id(&self) -> &'static str {
+        self.id
+    }
+
+    fn matches(&self, command: &Command, _session_metadata: &SessionMetadata) -> bool {
+        self.errors
+            .iter()
+            .any(|error| error.is_match(command.output))
+    }
+
+    fn generate_command_corrections<'a>(
+        &self,
+        command: &'a Command,
+        session_metadata: &'a SessionMetadata,
+    ) -> Option<Vec<RuleCorrection<'a>>> {
+        let unknown = self.unknown_subcommand(command)?;
+        let version = self.tool.version(session_metadata);
+
+        // The command the unknown subcommand was given to, e.g. `docker compose` for `docker compose upp`.
+        let input_parts = command.input_parts();
+        let end = input_parts
+            .iter()
+            .position(|part| part == unknown)
+            .unwrap_or(input_parts.len());
+        let (parent, invocation) = self.tool.command_for(&input_parts[..end], version);
+        // The help text is more accurate than the catalogue, when we have it.
+        let help = session_metadata
+            .help(&invocation)
+            .filter(|help| !help.subcommands.is_empty());
+        let subcommands = match &help {
+            Some(help) => help.subcommands.iter().map(String::as_str).collect_vec(),
+            None => parent.subcommand_names(version).collect_vec(),
+        };
+
+        let matcher = session_metadata.matcher(self.id());
+        let fix = get_single_closest_match(unknown, subcommands, matcher)?.to_owned();
+        new_commands_from_suggestions([fix], command.input_parts(), unknown)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::{basic_corrections, regular_corrections};
+    use crate::{Command, ExitCode, SessionMetadata};
+
+    #[test]
+    fn test_brew_unknown_command() {
+        assert_eq!(
+            basic_corrections("brew instll jq", "Error: Unknown command: instll"),
+            vec!["brew install jq"]
+        )
+    }
+
+    #[test]
+    fn test_docker_no_command() {
+        assert_eq!(
+            basic_corrections(
+                "docker img",
+                "docker: 'img' is not a docker command.
+                See 'docker --help'"
+            ),
+            vec!["docker image"]
+        )
+    }
+
+    #[test]
+    fn test_docker_nested_command() {
+        assert_eq!(
+            basic_corrections(
+                "docker compose upp -d",
+                r#"unknown docker command: "compose upp""#
+            ),
+            vec!["docker compose up -d"]
+        );
+        assert_eq!(
+            basic_corrections(
+                "docker --context prod network lss",
+                "docker: 'network lss' is not a docker command."
+            ),
+            vec!["docker --context prod network ls"]
+        );
+        assert_eq!(
+            basic_corrections(
+                "docker buildx imagetools inspct alpine",
+                r#"unknown docker command: "buildx imagetools inspct""#
+            ),
+            vec!["docker buildx imagetools inspect alpine"]
+        );
+    }
+
+    #[test]
+    fn test_compose_unknown_command() {
+        assert_eq!(
+            basic_corrections("docker-compose upp -d", "No such command: upp"),
+            vec!["docker-compose up -d"]
+        );
+        assert_eq!(
+            basic_corrections(
+                "podman-compose dwn",
+                "podman-compose: error: argument command: invalid choice: 'dwn' (choose from 'help', 'version', 'up', 'down')"
+            ),
+            vec!["podman-compose down"]
+        );
+    }
+
+    #[test]
+    fn test_podman_unknown_command() {
+        assert_eq!(
+            basic_corrections(
+                "podman volume prun",
+                "Error: unrecognized command `podman volume prun`
+Try 'podman volume --help' for more information"
+            ),
+            vec!["podman volume prune"]
+        );
+        assert_eq!(
+            basic_corrections(
+                "podman pod crate mypod",
+                "Error: unrecognized command `podman pod crate`"
+            ),
+            vec!["podman pod create mypod"]
+        );
+    }
+
+    #[test]
+    fn test_npm_unknown_command() {
+        assert_eq!(
+            basic_corrections(
+                "npm insll",
+                r#"npm insll
+                Unknown command: "insll"
+
+                To see a list of supported npm commands, run:
+                npm help"#
+            ),
+            vec!["npm install"]
+        )
+    }
+
+    #[test]
+    fn test_yarn_unknown_command() {
+        assert_eq!(
+            basic_corrections(
+                "yarn rn start",
+                r#"error Command "rn" not found.
+                info Visit https://yarnpkg.com/en/docs/cli/run for documentation about this command."#
+            ),
+            vec!["yarn run start"]
+        )
+    }
+
+    #[test]
+    fn test_cobra_unknown_command() {
+        assert_eq!(
+            basic_corrections(
+                "kubectl gett pods",
+                r#"error: unknown command "gett" for "kubectl""#
+            ),
+            vec!["kubectl get pods"]
+        );
+        assert_eq!(
+            basic_corrections(
+                "kubectl config get-contxts",
+                r#"error: unknown command "get-contxts" for "kubectl config""#
+            ),
+            vec!["kubectl config get-contexts"]
+        );
+        assert_eq!(
+            basic_corrections(
+                "helm repo updte",
+                r#"Error: unknown command "updte" for "helm repo""#
+            ),
+            vec!["helm repo update"]
+        );
+        assert_eq!(
+            basic_corrections(
+                "gh pr crate --fill",
+                "unknown command \"crate\" for \"gh pr\"\n\nDid you mean this?\n\tcreate"
+            ),
+            vec!["gh pr create --fill"]
+        );
+    }
+
+    #[test]
+    fn test_help_text() {
+        let command = || Command::new("npm sbm", r#"Unknown command: "sbm""#, ExitCode(1));
+        let mut session_metadata = SessionMetadata::new();
+        session_metadata.set_help_text("npm", "All commands:\n\n    access, sbom, search\n");
+        assert_eq!(
+            regular_corrections(command(), &session_metadata),
+            vec!["npm sbom"]
+        );
+
+        let mut session_metadata = SessionMetadata::new();
+        session_metadata.set_tool_version("npm", "10.2.4");
+        session_metadata.set_help_provider(|command| {
+            assert_eq!(command, "npm");
+            Some("All commands:\n\n    access, sbom, search\n".to_owned())
+        });
+        assert_eq!(
+            regular_corrections(command(), &session_metadata),
+            vec!["npm sbom"]
+        );
+    }
+
+    #[test]
+    fn test_nested_help_text() {
+        let mut session_metadata = SessionMetadata::new();
+        session_metadata.set_help_text("docker compose", "Commands:\n  alpha  Experimental\n");
+        let command = Command::new(
+            "docker compose alpah",
+            r#"unknown docker command: "compose alpah""#,
+            ExitCode(1),
+        );
+        assert_eq!(
+            regular_corrections(command, &session_metadata),
+            vec!["docker compose alpha"]
+        );
+    }
+
+    #[test]
+    fn test_tool_version() {
+        // `npm bin` was removed in npm 9.
+        let command = || Command::new("npm bim", r#"Unknown command: "bim""#, ExitCode(1));
+        assert_eq!(
+            regular_corrections(command(), &SessionMetadata::new()),
+            vec!["npm bin"]
+        );
+
+        let mut session_metadata = SessionMetadata::new();
+        session_metadata.set_tool_version("npm", "10.2.4");
+        assert_eq!(
+            regular_corrections(command(), &session_metadata),
+            Vec::<String>::new()
+        );
+    }
+}
@@ -3,14 +3,9 @@ use std::convert::AsRef;
 use std::fs;
 use std::path::{Component, Path};
 
-use difflib::get_close_matches;
-
+use crate::similarity::Matcher;
 use crate::RuleCorrection;
 
-// TODO: eventually make this configurable
-/// The score here refers to the ratio used by difflib.
-const MATCH_SCORE_CUTOFF: f32 = 0.6;
-
 /// Returns new commands where the to_replace string in
 /// input is replaced with the suggestions.
 pub(crate) fn new_commands_from_suggestions<'a>(
@@ -39,13 +34,51 @@ pub(crate) fn new_commands_from_suggestions<'a>(
     )
 }
 
-// TODO: the only reason possiblities is a Vec here is because
-// difflib::get_close_matches takes in a vec instead of an iterator.
-// This should also take an iter eventually.
-pub fn get_single_closest_match<'a>(to_match: &str, possiblities: Vec<&'a str>) -> Option<&'a str> {
-    get_close_matches(to_match, possiblities, 1, MATCH_SCORE_CUTOFF)
-        .first()
-        .copied()
+/// Returns the command with `flag` inserted after the first `part`, e.g. `--dry-run` after `push`.
+pub(crate) fn with_flag_after(
+    parts: &[impl AsRef<str>],
+    part: &str,
+    flag: &str,
+) -> Option<Vec<String>> {
+    let index = parts.iter().position(|p| p.as_ref() == part)?;
+    let mut new_parts = parts
+        .iter()
+        .map(|p| p.as_ref().to_owned())
+        .collect::<Vec<_>>();
+    new_parts.insert(index + 1, flag.to_owned());
+    Some(new_parts)
+}
+
+/// Adds a preview to each correction, made by inserting `flag` (e.g. `--dry-run`) after `part`.
+/// Only corrections given as command parts get a preview.
+pub(crate) fn with_flag_previews<'a>(
+    corrections: Vec<RuleCorrection<'a>>,
+    part: &str,
+    flag: &str,
+) -> Vec<RuleCorrection<'a>> {
+    corrections
+        .into_iter()
+        .map(|correction| {
+            let preview = match &correction {
+                RuleCorrection::CommandParts(parts) => with_flag_after(parts, part, flag),
+                _ => None,
+            };
+            match preview {
+                Some(preview) => correction.with_preview(preview),
+                None => correction,
+            }
+        })
+        .collect()
+}
+
+/// Returns the closest match to `to_match` among the possibilities (if any is close enough).
+/// Rules should pass in the matcher the client configured for them; see `SessionMetadata::matcher`.
+pub fn get_single_closest_match<'a>(
+    to_match: &str,
+    possiblities: impl IntoIterator<Item = &'a str>,
+    matcher: &Matcher,
+) -> Option<&'a str> {
+    matcher.closest_match(to_match, possiblities)
 }
 
 fn get_files_at_path_with_filter_at_level<F>(path: &Path, filter_at_level: F) -> Vec<String>
@@ -71,6 +104,7 @@ pub fn correct_path_at_every_level<F>(
     path_to_correct: impl AsRef<Path>,
     working_dir: impl AsRef<Path>,
     filter_at_level: F,
+    matcher: &Matcher,
 ) -> Option<String>
 where
     F: Fn(&Path) -> bool,
@@ -106,8 +140,8 @@ where
                     // closest matching one. Add the match to so_far and continue.
                     let dir_names =
                         get_files_at_path_with_filter_at_level(so_far.as_path(), &filter_at_level);
-                    let dir_name_strs = dir_names.iter().map(|d| d.as_str()).collect();
-                    let matches = get_single_closest_match(p.to_str()?, dir_name_strs)?;
+                    let dir_name_strs = dir_names.iter().map(|d| d.as_str());
+                    let matches = get_single_closest_match(p.to_str()?, dir_name_strs, matcher)?;
                     so_far = so_far.join(matches);
                 }
             }
@@ -142,6 +176,7 @@ mod test {
     use tempfile::tempdir;
 
     use crate::rules::util::new_commands_from_suggestions;
+    use crate::similarity::Matcher;
     use crate::test_utils::with_temp_directories;
     use crate::{Command, ExitCode};
 
@@ -162,6 +197,7 @@ mod test {
                 "aples/banannas/oranges/mans",
                 tmpdir.path().to_str().unwrap(),
                 |path| path.is_dir(),
+                &Matcher::default(),
             );
 
             assert_eq!(result.unwrap(), "apples/bananas/oranges/mangos");
@@ -176,6 +212,7 @@ mod test {
                 abs_path.to_owned() + "/aples/banannas/ranges/mans",
                 tmpdir.path().to_str().unwrap(),
                 |path| path.is_dir(),
+                &Matcher::default(),
             );
 
             assert_eq!(
@@ -192,6 +229,7 @@ mod test {
                 "aples/./banannas/../banango",
                 tmpdir.path().to_str().unwrap(),
                 |path| path.is_dir(),
+                &Matcher::default(),
             );
 
             assert_eq!(result.unwrap(), "apples/bananas");
@@ -224,7 +262,7 @@ mod test {
         let to_match = "poll";
         let possiblities = vec!["pull", "pole", "random"];
         assert_eq!(
-            get_single_closest_match(to_match, possiblities),
+            get_single_closest_match(to_match, possiblities, &Matcher::default()),
             Some("pull")
         )
     }
@@ -233,7 +271,10 @@ mod test {
     fn test_get_single_closest_match_no_match() {
         let to_match = "abc";
         let possiblities = vec!["pull", "pole", "random"];
-        assert_eq!(get_single_closest_match(to_match, possiblities), None)
+        assert_eq!(
+            get_single_closest_match(to_match, possiblities, &Matcher::default()),
+            None
+        )
     }
 
     #[test]
@@ -1,13 +1,11 @@
 use crate::rules::yarn::{
-    yarn_alias::YarnAlias, yarn_command_not_found::YarnCommandNotFound,
-    yarn_command_replaced::YarnCommandReplaced, yarn_help::YarnHelp,
+    yarn_alias::YarnAlias, yarn_command_replaced::YarnCommandReplaced, yarn_help::YarnHelp,
 };
 use crate::rules::Rule;
 
 use super::CommandGroup;
 
 mod yarn_alias;
-mod yarn_command_not_found;
 mod yarn_command_replaced;
 mod yarn_help;
 
@@ -15,7 +13,6 @@ pub(crate) fn command_group() -> CommandGroup {
     CommandGroup {
         command_names: &["yarn"],
         rules: vec![
-            YarnCommandNotFound.to_arc(),
             YarnAlias.to_arc(),
             YarnHelp.to_arc(),
             YarnCommandReplaced.to_arc(),
@@ -0,0 +1,507 @@
+use std::{cmp::Ordering, fmt, sync::Arc};
+
+use difflib::sequencematcher::SequenceMatcher;
+
+use crate::keyboard::Layout;
+
+/// The cutoff used when a client doesn't configure one. The score here
+/// refers to the ratio used by difflib.
+pub const DEFAULT_CUTOFF: f32 = 0.6;
+
+/// A way to score how similar two strings are, used to find close matches
+/// (e.g. the closest subcommand to a misspelled one).
+pub trait Similarity: Send + Sync {
+    /// Returns a score between 0 (nothing in common) and 1 (identical).
+    fn score(&self, to_match: &str, candidate: &str) -> f32;
+
+    /// Drops candidates that can't possibly score at least `cutoff`. This is purely an
+    /// optimization: implementations must never drop a candidate that would've made the cut.
+    fn prefilter<'a>(
+        &self,
+        _to_match: &str,
+        candidates: Vec<&'a str>,
+        _cutoff: f32,
+    ) -> Vec<&'a str> {
+        candidates
+    }
+
+    /// The same similarity for the given keyboard layout, if it depends on the layout
+    /// (e.g. `KeyboardDistance`).
+    fn with_layout(&self, _layout: Layout) -> Option<Arc<dyn Similarity>> {
+        None
+    }
+}
+
+/// The ratio computed by difflib's `SequenceMatcher` (i.e. Python's `difflib.get_close_matches`).
+/// This is the default.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DifflibRatio;
+
+impl Similarity for DifflibRatio {
+    fn score(&self, to_match: &str, candidate: &str) -> f32 {
+        // Note: difflib's ratio isn't symmetric. The argument order matches `get_close_matches`.
+        SequenceMatcher::new(candidate, to_match).ratio()
+    }
+
+    /// Difflib's ratio is 2 * M / (len(a) + len(b)), where M is the number of matching bytes.
+    /// M is at most the length of the shorter string, and at most the size of the intersection
+    /// of both strings' byte multisets, so if either upper bound scores below the cutoff, so
+    /// does the real ratio.
+    fn prefilter<'a>(&self, to_match: &str, candidates: Vec<&'a str>, cutoff: f32) -> Vec<&'a str> {
+        let to_match = to_match.as_bytes();
+        let mut to_match_counts = [0usize; 256];
+        for &byte in to_match {
+            to_match_counts[byte as usize] += 1;
+        }
+
+        // Reused across candidates; only the entries we touch get reset.
+        let mut available = to_match_counts;
+        candidates
+            .into_iter()
+            .filter(|candidate| {
+                let candidate = candidate.as_bytes();
+                let length = to_match.len() + candidate.len();
+                if difflib_ratio(to_match.len().min(candidate.len()), length) < cutoff {
+                    return false;
+                }
+
+                let mut matches = 0;
+                for &byte in candidate {
+                    let count = &mut available[byte as usize];
+                    if *count > 0 {
+                        *count -= 1;
+                        matches += 1;
+                    }
+                }
+                for &byte in candidate {
+                    available[byte as usize] = to_match_counts[byte as usize];
+                }
+
+                difflib_ratio(matches, length) >= cutoff
+            })
+            .collect()
+    }
+}
+
+/// Same formula difflib uses for its ratio, so that bounds computed
+/// here compare exactly like difflib's scores.
+fn difflib_ratio(matches: usize, length: usize) -> f32 {
+    if length == 0 {
+        return 1.0;
+    }
+    2.0 * matches as f32 / length as f32
+}
+
+/// The (unrestricted) Damerau-Levenshtein distance, normalized by the length of the longer string.
+/// Unlike difflib, a transposition ("gti" vs "git") only counts as a single edit.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DamerauLevenshtein;
+
+impl Similarity for DamerauLevenshtein {
+    fn score(&self, to_match: &str, candidate: &str) -> f32 {
+        let a = to_match.chars().collect::<Vec<_>>();
+        let b = candidate.chars().collect::<Vec<_>>();
+        normalize_distance(damerau_levenshtein(&a, &b) as f32, a.len(), b.len())
+    }
+}
+
+fn damerau_levenshtein(a: &[char], b: &[char]) -> usize {
+    use std::collections::HashMap;
+
+    // See https://en.wikipedia.org/wiki/Damerau%E2%80%93Levenshtein_distance#Distance_with_adjacent_transpositions
+    let max_distance = a.len() + b.len();
+    let width = b.len() + 2;
+    let mut d = vec![0; (a.len() + 2) * width];
+    let idx = |i: usize, j: usize| i * width + j;
+
+    d[idx(0, 0)] = max_distance;
+    for i in 0..=a.len() {
+        d[idx(i + 1, 0)] = max_distance;
+        d[idx(i + 1, 1)] = i;
+    }
+    for j in 0..=b.len() {
+        d[idx(0, j + 1)] = max_distance;
+        d[idx(1, j + 1)] = j;
+    }
+
+    let mut last_row_with = HashMap::new();
+    for i in 1..=a.len() {
+        let mut last_match_col = 0;
+        for j in 1..=b.len() {
+            let last_match_row = *last_row_with.get(&b[j - 1]).unwrap_or(&0);
+            let prev_match_col = last_match_col;
+            let cost = if a[i - 1] == b[j - 1] {
+                last_match_col = j;
+                0
+            } else {
+                1
+            };
+            d[idx(i + 1, j + 1)] = (d[idx(i, j)] + cost)
+                .min(d[idx(i + 1, j)] + 1)
+                .min(d[idx(i, j + 1)] + 1)
+                .min(
+                    d[idx(last_match_row, prev_match_col)]
+                        + (i - last_match_row - 1)
+                        + 1
+                        + (j - prev_match_col - 1),
+                );
+        }
+        last_row_with.insert(a[i - 1], i);
+    }
+
+    d[idx(a.len() + 1, b.len() + 1)]
+}
+
+/// The Jaro-Winkler similarity, which favors strings that share a prefix.
+/// Works well for short words (like command names).
+#[derive(Debug, Clone, Copy)]
+pub struct JaroWinkler {
+    /// How much a common prefix (of up to 4 characters) boosts the score. Should be at most 0.25.
+    pub prefix_scale: f32,
+}
+
+impl Default for JaroWinkler {
+    fn default() -> Self {
+        Self { prefix_scale: 0.1 }
+    }
+}
+
+impl Similarity for JaroWinkler {
+    fn score(&self, to_match: &str, candidate: &str) -> f32 {
+        let a = to_match.chars().collect::<Vec<_>>();
+        let b = candidate.chars().collect::<Vec<_>>();
+        let jaro = jaro(&a, &b);
+        let prefix_len = a.iter().zip(&b).take(4).take_while(|(a, b)| a == b).count();
+        jaro + prefix_len as f32 * self.prefix_scale * (1.0 - jaro)
+    }
+}
+
+fn jaro(a: &[char], b: &[char]) -> f32 {
+    if a.is_empty() && b.is_empty() {
+        return 1.0;
+    }
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+
+    let match_distance = (a.len().max(b.len()) / 2).saturating_sub(1);
+    let mut a_matches = vec![false; a.len()];
+    let mut b_matches = vec![false; b.len()];
+    let mut matches = 0;
+    for (i, a_char) in a.iter().enumerate() {
+        let start = i.saturating_sub(match_distance);
+        let end = (i + match_distance + 1).min(b.len());
+        for j in start..end {
+            if !b_matches[j] && b[j] == *a_char {
+                a_matches[i] = true;
+                b_matches[j] = true;
+                matches += 1;
+                break;
+            }
+        }
+    }
+    if matches == 0 {
+        return 0.0;
+    }
+
+    let a_matched = a
+        .iter()
+        .zip(&a_matches)
+        .filter(|(_, m)| **m)
+        .map(|(c, _)| c);
+    let b_matched = b
+        .iter()
+        .zip(&b_matches)
+        .filter(|(_, m)| **m)
+        .map(|(c, _)| c);
+    let transpositions = a_matched.zip(b_matched).filter(|(a, b)| a != b).count() / 2;
+
+    let matches = matches as f32;
+    (matches / a.len() as f32
+        + matches / b.len() as f32
+        + (matches - transpositions as f32) / matches)
+        / 3.0
+}
+
+/// An edit distance (with transpositions) where substituting a character with one on an
+/// adjacent key is cheaper than any other substitution, since that's the most likely typo.
+/// Normalized by the length of the longer string.
+#[derive(Debug, Clone, Copy)]
+pub struct KeyboardDistance {
+    /// The cost of substituting a character with one on an adjacent key (any other edit costs 1).
+    pub adjacent_key_cost: f32,
+    /// The layout used to tell which keys are adjacent.
+    pub layout: Layout,
+}
+
+impl Default for KeyboardDistance {
+    fn default() -> Self {
+        Self {
+            adjacent_key_cost: 0.5,
+            layout: Layout::default(),
+        }
+    }
+}
+
+impl Similarity for KeyboardDistance {
+    fn score(&self, to_match: &str, candidate: &str) -> f32 {
+        let a = to_match.chars().collect::<Vec<_>>();
+        let b = candidate.chars().collect::<Vec<_>>();
+        let substitution_cost = |x: char, y: char| {
+            if x == y {
+                0.0
+            } else if self.layout.are_adjacent(x, y) {
+                self.adjacent_key_cost
+            } else {
+                1.0
+            }
+        };
+
+        // Optimal string alignment distance with weighted substitutions.
+        let width = b.len() + 1;
+        let mut d = vec![0.0f32; (a.len() + 1) * width];
+        let idx = |i: usize, j: usize| i * width + j;
+        for i in 0..=a.len() {
+            d[idx(i, 0)] = i as f32;
+        }
+        for j in 0..=b.len() {
+            d[idx(0, j)] = j as f32;
+        }
+        for i in 1..=a.len() {
+            for j in 1..=b.len() {
+                let mut distance = (d[idx(i - 1, j)] + 1.0)
+                    .min(d[idx(i, j - 1)] + 1.0)
+                    .min(d[idx(i - 1, j - 1)] + substitution_cost(a[i - 1], b[j - 1]));
+                if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                    distance = distance.min(d[idx(i - 2, j - 2)] + 1.0);
+                }
+                d[idx(i, j)] = distance;
+            }
+        }
+
+        normalize_distance(d[idx(a.len(), b.len())], a.len(), b.len())
+    }
+
+    fn with_layout(&self, layout: Layout) -> Option<Arc<dyn Similarity>> {
+        Some(Arc::new(Self { layout, ..*self }))
+    }
+}
+
+fn normalize_distance(distance: f32, a_len: usize, b_len: usize) -> f32 {
+    let max_len = a_len.max(b_len);
+    if max_len == 0 {
+        return 1.0;
+    }
+    (1.0 - distance / max_len as f32).max(0.0)
+}
+
+/// A candidate along with its similarity score.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScoredMatch<'a> {
+    pub candidate: &'a str,
+    pub score: f32,
+}
+
+/// A similarity backend along with the minimum score for a candidate to count as a match.
+/// Clients can configure one globally and per rule; see `SessionMetadata::set_matcher`.
+#[derive(Clone)]
+pub struct Matcher {
+    similarity: Arc<dyn Similarity>,
+    cutoff: f32,
+    /// Breaks ties between candidates in favor of substitutions with adjacent keys.
+    layout: Option<Layout>,
+}
+
+impl Matcher {
+    pub fn new(similarity: impl Similarity + 'static, cutoff: f32) -> Self {
+        Self {
+            similarity: Arc::new(similarity),
+            cutoff: cutoff.clamp(0.0, 1.0),
+            layout: None,
+        }
+    }
+
+    /// The same matcher for the given keyboard layout: candidates with the same score are
+    /// ordered by how close they are on the keyboard (see `KeyboardDistance`), and
+    /// similarities that depend on the layout use it.
+    pub fn with_layout(&self, layout: Layout) -> Self {
+        Self {
+            similarity: self
+                .similarity
+                .with_layout(layout)
+                .unwrap_or_else(|| self.similarity.clone()),
+            cutoff: self.cutoff,
+            layout: Some(layout),
+        }
+    }
+
+    pub fn cutoff(&self) -> f32 {
+        self.cutoff
+    }
+
+    pub fn score(&self, to_match: &str, candidate: &str) -> f32 {
+        self.similarity.score(to_match, candidate)
+    }
+
+    /// Returns up to `n` candidates that score at least the cutoff, best first.
+    /// Candidates with the same score stay in their original order.
+    pub fn closest_matches<'a>(
+        &self,
+        to_match: &str,
+        candidates: impl IntoIterator<Item = &'a str>,
+        n: usize,
+    ) -> Vec<ScoredMatch<'a>> {
+        let candidates =
+            self.similarity
+                .prefilter(to_match, candidates.into_iter().collect(), self.cutoff);
+
+        let mut matches = candidates
+            .into_iter()
+            .filter_map(|candidate| {
+                let score = self.similarity.score(to_match, candidate);
+                (score >= self.cutoff).then_some(ScoredMatch { candidate, score })
+            })
+            .collect::<Vec<_>>();
+        let keyboard_distance = self.layout.map(|layout| KeyboardDistance {
+            layout,
+            ..Default::default()
+        });
+        matches.sort_by(|a, b| {
+            let by_keyboard = || match keyboard_distance {
+                Some(distance) => distance
+                    .score(to_match, b.candidate)
+                    .total_cmp(&distance.score(to_match, a.candidate)),
+                None => Ordering::Equal,
+            };
+            b.score.total_cmp(&a.score).then_with(by_keyboard)
+        });
+        matches.truncate(n);
+        matches
+    }
+
+    pub fn closest_match<'a>(
+        &self,
+        to_match: &str,
+        candidates: impl IntoIterator<Item = &'a str>,
+    ) -> Option<&'a str> {
+        self.closest_matches(to_match, candidates, 1)
+            .first()
+            .map(|scored_match| scored_match.candidate)
+    }
+}
+
+impl Default for Matcher {
+    fn default() -> Self {
+        Matcher::new(DifflibRatio, DEFAULT_CUTOFF)
+    }
+}
+
+impl fmt::Debug for Matcher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Matcher")
+            .field("cutoff", &self.cutoff)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use difflib::get_close_matches;
+    use proptest::prelude::*;
+
+    use super::{
+        DamerauLevenshtein, DifflibRatio, JaroWinkler, KeyboardDistance, Layout, Matcher,
+        Similarity,
+    };
+
+    #[test]
+    fn test_difflib_ratio_prefilter_drops_candidates() {
+        let candidates = vec![
+            "git", "gt", "tig", "gitk", "grep", "cargo", "", "g", "gtihub",
+        ];
+        let prefiltered = DifflibRatio.prefilter("gti", candidates.clone(), 0.6);
+
+        assert!(prefiltered.len() < candidates.len());
+        assert_eq!(
+            get_close_matches("gti", prefiltered, 10, 0.6),
+            get_close_matches("gti", candidates, 10, 0.6)
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn test_default_matcher_is_the_same_as_difflib(
+            to_match in "[a-e-]{0,8}|\\PC{0,6}",
+            candidates in proptest::collection::vec("[a-e-]{0,8}|\\PC{0,6}", 0..30),
+            cutoff in 0.0f32..=1.0,
+        ) {
+            let candidates = candidates.iter().map(String::as_str).collect::<Vec<_>>();
+            let matches = Matcher::new(DifflibRatio, cutoff)
+                .closest_matches(&to_match, candidates.clone(), 5)
+                .into_iter()
+                .map(|scored_match| scored_match.candidate)
+                .collect::<Vec<_>>();
+            prop_assert_eq!(matches, get_close_matches(&to_match, candidates, 5, cutoff));
+        }
+    }
+
+    #[test]
+    fn test_damerau_levenshtein() {
+        assert_eq!(DamerauLevenshtein.score("git", "git"), 1.0);
+        // A transposition is a single edit.
+        assert!((DamerauLevenshtein.score("gti", "git") - 2.0 / 3.0).abs() < f32::EPSILON);
+        assert!((DamerauLevenshtein.score("ca", "abc") - 1.0 / 3.0).abs() < f32::EPSILON);
+        assert_eq!(DamerauLevenshtein.score("", ""), 1.0);
+        assert_eq!(DamerauLevenshtein.score("abc", ""), 0.0);
+    }
+
+    #[test]
+    fn test_jaro_winkler() {
+        let jaro_winkler = JaroWinkler::default();
+        assert_eq!(jaro_winkler.score("git", "git"), 1.0);
+        assert!((jaro_winkler.score("martha", "marhta") - 0.9611).abs() < 0.001);
+        assert!((jaro_winkler.score("dixon", "dicksonx") - 0.8133).abs() < 0.001);
+        assert_eq!(jaro_winkler.score("abc", "xyz"), 0.0);
+    }
+
+    #[test]
+    fn test_keyboard_distance_prefers_adjacent_keys() {
+        let keyboard = KeyboardDistance::default();
+        // "h" is next to "g", "p" isn't.
+        assert!(keyboard.score("hit", "git") > keyboard.score("pit", "git"));
+        assert_eq!(keyboard.score("hit", "git"), 1.0 - 0.5 / 3.0);
+        assert_eq!(keyboard.score("git", "git"), 1.0);
+
+        // On Dvorak, "h" is next to "t" instead.
+        let dvorak = KeyboardDistance {
+            layout: Layout::Dvorak,
+            ..KeyboardDistance::default()
+        };
+        assert!(dvorak.score("gih", "git") > keyboard.score("gih", "git"));
+    }
+
+    #[test]
+    fn test_matcher_ranks_by_score() {
+        let matcher = Matcher::new(KeyboardDistance::default(), 0.5);
+        let matches = matcher.closest_matches("hit", ["pit", "git", "hits"], 3);
+        assert_eq!(
+            matches.iter().map(|m| m.candidate).collect::<Vec<_>>(),
+            vec!["git", "hits", "pit"]
+        );
+        assert!(matches[0].score > matches[1].score);
+        assert_eq!(matcher.closest_match("zzz", ["git"]), None);
+    }
+
+    #[test]
+    fn test_matcher_with_layout() {
+        // Both score the same, but "u" is next to "i" (and not "a") on QWERTY.
+        let matcher = Matcher::default();
+        assert_eq!(matcher.closest_match("gut", ["gat", "git"]), Some("gat"));
+        let qwerty = matcher.with_layout(Layout::Qwerty);
+        assert_eq!(qwerty.closest_match("gut", ["gat", "git"]), Some("git"));
+
+        // Similarities that depend on the layout use the matcher's.
+        let dvorak = Matcher::new(KeyboardDistance::default(), 0.5).with_layout(Layout::Dvorak);
+        assert!(dvorak.score("gih", "git") > KeyboardDistance::default().score("gih", "git"));
+    }
+}
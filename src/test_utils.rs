@@ -1,8 +1,14 @@
-use std::{fs, path::Path};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
 
 use tempfile::{tempdir, TempDir};
 
-use crate::{correct_command, Command, ExitCode, SessionMetadata};
+use crate::git::{GitOperation, GitState};
+use crate::{
+    apply_rules, correct_command, rules, Command, ExitCode, SessionMetadata, SessionType, Shell,
+};
 
 /// Doesn't make use of any session metadata.
 pub fn basic_corrections(input: &str, output: &str) -> Vec<String> {
@@ -11,6 +17,15 @@ pub fn basic_corrections(input: &str, output: &str) -> Vec<String> {
     regular_corrections(command, &metadata)
 }
 
+/// Like `basic_corrections`, but returns the preview of each correction.
+pub fn basic_previews(input: &str, output: &str) -> Vec<Option<String>> {
+    let command = Command::new(input, output, ExitCode(1));
+    correct_command(command, &SessionMetadata::new())
+        .into_iter()
+        .map(|correction| correction.preview)
+        .collect()
+}
+
 /// Same API as `correct_command` but simpler return type.
 pub fn regular_corrections(command: Command, metadata: &SessionMetadata) -> Vec<String> {
     correct_command(command, metadata)
@@ -19,6 +34,25 @@ pub fn regular_corrections(command: Command, metadata: &SessionMetadata) -> Vec<
         .collect()
 }
 
+/// Same as `regular_corrections`, but only applies the rules with the given id
+/// (so corrections from other rules don't get in the way). Some rules share an id,
+/// e.g. the cargo and generic `NoCommand` rules.
+pub fn rule_corrections(
+    rule_id: &str,
+    command: Command,
+    metadata: &SessionMetadata,
+) -> Vec<String> {
+    let rules = rules::all_rules()
+        .filter(|rule| rule.id() == rule_id)
+        .collect::<Vec<_>>();
+    assert!(!rules.is_empty(), "no rule with id {rule_id}");
+
+    apply_rules(rules.into_iter(), &command, metadata, None)
+        .into_iter()
+        .map(|correction| correction.command)
+        .collect()
+}
+
 pub fn with_temp_directories(dir_paths: &[impl AsRef<Path>], test: impl Fn(TempDir)) {
     let tmpdir = tempdir().unwrap();
     for path in dir_paths {
@@ -27,3 +61,276 @@ pub fn with_temp_directories(dir_paths: &[impl AsRef<Path>], test: impl Fn(TempD
 
     test(tmpdir)
 }
+
+/*
+Fixtures are real captured failures, stored under `tests/fixtures/<RuleId>/<case name>/`.
+Each fixture is checked against the rule named by its parent directory _in isolation_.
+A fixture directory contains:
+- `input`: the command the user ran.
+- `output`: the output of the command.
+- `exit_code` (optional): defaults to 1.
+- `metadata` (optional): session metadata, one `key: value` per line. The keys `executables`,
+  `aliases`, `functions`, `builtins`, `git_branches`, `git_remotes` and `git_tags` take
+  whitespace separated lists, `git_remote_branches` takes a remote followed by its branches
+  (and can be repeated), `git_current_branch` takes a branch, `git_operation` is one of
+  merge/rebase/cherry-pick/revert/am/bisect, `git_aliases` and `git_remote_urls` take
+  whitespace separated `alias=subcommand` and `remote=url` pairs,
+  `history` takes one entry per line (and can be repeated), `shell` is one of bash/zsh/fish and
+  `session_type` is one of local/remote.
+- `cwd/` (optional): a directory tree that's copied to a temporary directory, which becomes the
+  working directory of the command. Git can't track empty directories, so use `.keep` files.
+- `expected`: the expected corrections, one per line.
+`{cwd}` in `input`, `output` and `expected` is replaced by the temporary working directory.
+
+Run with `FIXTURE_RULE=<RuleId>` to only check one rule's fixtures, and with `BLESS=1`
+to (re)write `expected` from the current corrections.
+*/
+
+const CWD_PLACEHOLDER: &str = "{cwd}";
+
+pub struct Fixture {
+    pub rule_id: String,
+    pub path: PathBuf,
+    input: String,
+    output: String,
+    exit_code: usize,
+    metadata: Vec<(String, String)>,
+    expected: Vec<String>,
+}
+
+impl Fixture {
+    pub fn load(path: impl AsRef<Path>) -> Self {
+        let path = path.as_ref();
+        let read = |name: &str| fs::read_to_string(path.join(name)).ok();
+        let rule_id = path
+            .parent()
+            .and_then(Path::file_name)
+            .and_then(|rule_id| rule_id.to_str())
+            .expect("fixtures live in a directory named after the rule")
+            .to_owned();
+
+        Self {
+            rule_id,
+            path: path.to_owned(),
+            input: read("input").expect("fixture is missing `input`"),
+            output: read("output").unwrap_or_default(),
+            exit_code: read("exit_code")
+                .map(|code| code.trim().parse().expect("invalid `exit_code`"))
+                .unwrap_or(1),
+            metadata: read("metadata")
+                .unwrap_or_default()
+                .lines()
+                .filter_map(|line| line.split_once(':'))
+                .map(|(key, value)| (key.trim().to_owned(), value.trim().to_owned()))
+                .collect(),
+            expected: read("expected")
+                .unwrap_or_default()
+                .lines()
+                .filter(|line| !line.is_empty())
+                .map(ToOwned::to_owned)
+                .collect(),
+        }
+    }
+
+    fn session_metadata(&self) -> SessionMetadata<'_> {
+        let mut metadata = SessionMetadata::new();
+        let list = |key: &'static str| {
+            self.metadata
+                .iter()
+                .filter(move |(k, _)| k == key)
+                .flat_map(|(_, value)| value.split_whitespace())
+        };
+
+        metadata.set_executables(list("executables"));
+        metadata.set_aliases(list("aliases"));
+        metadata.set_functions(list("functions"));
+        metadata.set_builtins(list("builtins"));
+        metadata.set_git_branches(list("git_branches"));
+        metadata.set_docker_containers(list("docker_containers"));
+        metadata.set_docker_images(list("docker_images"));
+        // Fixtures don't depend on the user's git config.
+        metadata.set_git_aliases(list("git_aliases").map(|alias| {
+            alias
+                .split_once('=')
+                .unwrap_or_else(|| panic!("invalid `git_aliases`: {alias}"))
+        }));
+        let to_owned = |values: &mut dyn Iterator<Item = &str>| values.map(str::to_owned).collect();
+        let git_keys = [
+            "git_current_branch",
+            "git_remotes",
+            "git_remote_urls",
+            "git_remote_branches",
+            "git_tags",
+            "git_operation",
+        ];
+        if self
+            .metadata
+            .iter()
+            .any(|(key, _)| git_keys.contains(&key.as_str()))
+        {
+            metadata.set_git_state(GitState {
+                current_branch: list("git_current_branch").next().map(str::to_owned),
+                remotes: to_owned(&mut list("git_remotes")),
+                remote_urls: list("git_remote_urls")
+                    .map(|remote_url| {
+                        let (remote, url) = remote_url
+                            .split_once('=')
+                            .unwrap_or_else(|| panic!("invalid `git_remote_urls`: {remote_url}"));
+                        (remote.to_owned(), url.to_owned())
+                    })
+                    .collect(),
+                remote_branches: self
+                    .metadata
+                    .iter()
+                    .filter(|(key, _)| key == "git_remote_branches")
+                    .filter_map(|(_, value)| {
+                        let mut values = value.split_whitespace();
+                        Some((values.next()?.to_owned(), to_owned(&mut values)))
+                    })
+                    .collect(),
+                tags: to_owned(&mut list("git_tags")),
+                operation: list("git_operation")
+                    .next()
+                    .map(|operation| match operation {
+                        "merge" => GitOperation::Merge,
+                        "rebase" => GitOperation::Rebase,
+                        "cherry-pick" => GitOperation::CherryPick,
+                        "revert" => GitOperation::Revert,
+                        "am" => GitOperation::Am,
+                        "bisect" => GitOperation::Bisect,
+                        _ => panic!("invalid `git_operation`: {operation}"),
+                    }),
+                ..Default::default()
+            });
+        }
+        metadata.set_history(
+            self.metadata
+                .iter()
+                .filter(|(key, _)| key == "history")
+                .map(|(_, value)| value.as_str()),
+        );
+
+        for (key, value) in &self.metadata {
+            match (key.as_str(), value.as_str()) {
+                ("shell", "bash") => metadata.set_shell(Shell::Bash),
+                ("shell", "zsh") => metadata.set_shell(Shell::Zsh),
+                ("shell", "fish") => metadata.set_shell(Shell::Fish),
+                ("session_type", "local") => metadata.set_session_type(SessionType::Local),
+                ("session_type", "remote") => metadata.set_session_type(SessionType::Remote),
+                ("shell" | "session_type", _) => panic!("invalid `{key}`: {value}"),
+                _ => {}
+            }
+        }
+        metadata
+    }
+
+    /// Runs the fixture's rule (in isolation) and returns the corrections, with the
+    /// temporary working directory replaced by `{cwd}`.
+    pub fn run(&self) -> Vec<String> {
+        let cwd = tempdir().unwrap();
+        let cwd_path = cwd.path().to_str().unwrap();
+        let fixture_cwd = self.path.join("cwd");
+        if fixture_cwd.is_dir() {
+            copy_dir_all(&fixture_cwd, cwd.path());
+        }
+
+        let input = self.input.replace(CWD_PLACEHOLDER, cwd_path);
+        let output = self.output.replace(CWD_PLACEHOLDER, cwd_path);
+        let mut command = Command::new(&input, &output, ExitCode(self.exit_code));
+        if fixture_cwd.is_dir() {
+            command = command.set_working_dir(cwd_path);
+        }
+
+        rule_corrections(&self.rule_id, command, &self.session_metadata())
+            .into_iter()
+            .map(|correction| correction.replace(cwd_path, CWD_PLACEHOLDER))
+            .collect()
+    }
+
+    /// Overwrites `expected` with the given corrections.
+    pub fn bless(&self, corrections: &[String]) {
+        let expected = corrections
+            .iter()
+            .map(|correction| format!("{correction}\n"))
+            .collect::<String>();
+        fs::write(self.path.join("expected"), expected).unwrap();
+    }
+}
+
+pub fn fixtures_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures")
+}
+
+/// Loads the fixtures for every rule (or for a single rule, if `rule_id` is given).
+pub fn load_fixtures(rule_id: Option<&str>) -> Vec<Fixture> {
+    let mut fixture_paths = fs::read_dir(fixtures_dir())
+        .unwrap()
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|rule_dir| {
+            rule_dir.is_dir()
+                && rule_id.is_none_or(|rule_id| rule_dir.file_name() == Some(rule_id.as_ref()))
+        })
+        .flat_map(|rule_dir| fs::read_dir(rule_dir).unwrap())
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.is_dir())
+        .collect::<Vec<_>>();
+    fixture_paths.sort();
+
+    fixture_paths.into_iter().map(Fixture::load).collect()
+}
+
+fn copy_dir_all(from: &Path, to: &Path) {
+    fs::create_dir_all(to).unwrap();
+    for entry in fs::read_dir(from).unwrap() {
+        let entry = entry.unwrap();
+        let dest = to.join(entry.file_name());
+        if entry.path().is_dir() {
+            copy_dir_all(&entry.path(), &dest);
+        } else if entry.file_name() != ".keep" {
+            fs::copy(entry.path(), dest).unwrap();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use super::load_fixtures;
+
+    #[test]
+    fn test_fixtures() {
+        let rule_id = env::var("FIXTURE_RULE").ok();
+        let bless = env::var("BLESS").is_ok();
+
+        let fixtures = load_fixtures(rule_id.as_deref());
+        assert!(!fixtures.is_empty(), "no fixtures found");
+
+        let failures = fixtures
+            .iter()
+            .filter_map(|fixture| {
+                let corrections = fixture.run();
+                if bless {
+                    fixture.bless(&corrections);
+                    None
+                } else {
+                    (corrections != fixture.expected).then(|| {
+                        format!(
+                            "{}\n  expected: {:?}\n    actual: {:?}",
+                            fixture.path.display(),
+                            fixture.expected,
+                            corrections
+                        )
+                    })
+                }
+            })
+            .collect::<Vec<_>>();
+
+        assert!(
+            failures.is_empty(),
+            "{} fixture(s) failed:\n{}",
+            failures.len(),
+            failures.join("\n")
+        );
+    }
+}
@@ -0,0 +1,177 @@
+use std::fmt;
+
+/// A log of how every rule was evaluated while correcting a command. This is
+/// meant for debugging why a correction did (or didn't) show up; see `correct_command_traced`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EvaluationTrace {
+    /// The (trimmed) input of the command that was corrected.
+    pub input: String,
+    /// One entry per rule, in the order the rules were evaluated.
+    pub evaluations: Vec<RuleEvaluation>,
+}
+
+impl EvaluationTrace {
+    pub(crate) fn new(input: &str) -> Self {
+        Self {
+            input: input.to_owned(),
+            evaluations: vec![],
+        }
+    }
+
+    pub(crate) fn record(&mut self, rule_id: &'static str, outcome: RuleOutcome) {
+        self.evaluations.push(RuleEvaluation { rule_id, outcome });
+    }
+
+    /// Returns the evaluation for the rule with the given id (if the rule was evaluated).
+    pub fn get(&self, rule_id: &str) -> Option<&RuleEvaluation> {
+        self.evaluations
+            .iter()
+            .find(|evaluation| evaluation.rule_id == rule_id)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleEvaluation {
+    pub rule_id: &'static str,
+    pub outcome: RuleOutcome,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RuleOutcome {
+    /// The rule wasn't considered at all (e.g. most rules skip commands that succeeded).
+    NotConsidered,
+    /// The rule was considered but `matches` returned false.
+    NotMatched,
+    /// The rule matched but didn't generate any corrections.
+    NoCorrections,
+    /// The rule matched and generated these corrections. Note that some of them
+    /// may have been dropped; see `CorrectionStatus`.
+    Corrected(Vec<TracedCorrection>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TracedCorrection {
+    pub command: String,
+    pub status: CorrectionStatus,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CorrectionStatus {
+    /// The correction was returned to the caller.
+    Accepted,
+    /// Dropped because it looks exactly like the original command input.
+    SameAsInput,
+    /// Dropped because an earlier rule (the one included here) already suggested it.
+    Duplicate(&'static str),
+    /// Dropped because it couldn't be turned into a shell command (e.g. a part contains a nul byte).
+    /// In this case, the `command` is the debug representation of the correction.
+    Unrepresentable,
+}
+
+impl fmt::Display for EvaluationTrace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Rule evaluation for `{}`:", self.input)?;
+        for evaluation in &self.evaluations {
+            write!(f, "{evaluation}")?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for RuleEvaluation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use RuleOutcome::*;
+        let rule_id = self.rule_id;
+        match &self.outcome {
+            NotConsidered => writeln!(f, "  {rule_id}: not considered"),
+            NotMatched => writeln!(f, "  {rule_id}: did not match"),
+            NoCorrections => writeln!(f, "  {rule_id}: matched, no corrections"),
+            Corrected(corrections) => {
+                writeln!(f, "  {rule_id}: matched")?;
+                for correction in corrections {
+                    writeln!(f, "    {correction}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl fmt::Display for TracedCorrection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use CorrectionStatus::*;
+        let command = &self.command;
+        match self.status {
+            Accepted => write!(f, "+ {command}"),
+            SameAsInput => write!(f, "- {command} (same as input)"),
+            Duplicate(rule_id) => write!(f, "- {command} (duplicate of {rule_id})"),
+            Unrepresentable => write!(f, "- {command} (not a valid shell command)"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CorrectionStatus, RuleOutcome, TracedCorrection};
+    use crate::{correct_command_traced, Command, ExitCode, SessionMetadata};
+
+    #[test]
+    fn test_trace_records_every_rule() {
+        let command = Command::new("git git status", "some random error", ExitCode(1));
+        let (corrections, trace) = correct_command_traced(command, &SessionMetadata::new());
+
+        assert_eq!(corrections.len(), 1);
+        assert_eq!(trace.input, "git git status");
+        assert_eq!(
+            trace.get("GitCheckout").map(|e| &e.outcome),
+            Some(&RuleOutcome::NotMatched)
+        );
+        assert_eq!(
+            trace.get("Repetition").map(|e| &e.outcome),
+            Some(&RuleOutcome::Corrected(vec![TracedCorrection {
+                command: "git status".to_owned(),
+                status: CorrectionStatus::Accepted,
+            }]))
+        );
+    }
+
+    #[test]
+    fn test_trace_for_successful_command() {
+        let command = Command::new("git git status", "", ExitCode(0));
+        let (corrections, trace) = correct_command_traced(command, &SessionMetadata::new());
+
+        assert!(corrections.is_empty());
+        assert!(trace
+            .evaluations
+            .iter()
+            .all(|evaluation| evaluation.outcome == RuleOutcome::NotConsidered));
+    }
+
+    #[test]
+    fn test_trace_records_duplicates() {
+        // Both LeadingShellPrompt and Repetition suggest dropping the first `$`.
+        let command = Command::new("$ $ ls", "zsh: command not found: $", ExitCode(127));
+        let (corrections, trace) = correct_command_traced(command, &SessionMetadata::new());
+
+        assert_eq!(corrections.len(), 1);
+        assert_eq!(corrections[0].rule_applied, "LeadingShellPrompt");
+        assert_eq!(
+            trace.get("Repetition").map(|e| &e.outcome),
+            Some(&RuleOutcome::Corrected(vec![TracedCorrection {
+                command: "'$' ls".to_owned(),
+                status: CorrectionStatus::Duplicate("LeadingShellPrompt"),
+            }]))
+        );
+    }
+
+    #[test]
+    fn test_trace_display() {
+        let command = Command::new("git git status", "some random error", ExitCode(1));
+        let (_, trace) = correct_command_traced(command, &SessionMetadata::new());
+        let formatted = trace.to_string();
+
+        assert!(formatted.starts_with("Rule evaluation for `git git status`:\n"));
+        assert!(formatted.contains("  GitCheckout: did not match\n"));
+        assert!(formatted.contains("  Repetition: matched\n    + git status\n"));
+    }
+}